@@ -0,0 +1,144 @@
+//! # Inertia Calibration
+//! estimasi inertia efektif roller dan koefisien gesek dari rekaman spin-down.
+//! setiap sample memenuhi persamaan gerak roller
+//! `T_ref = (I + I_tambahan) * α + c_coulomb + c_viscous * ω`,
+//! dengan `T_ref` torsi referensi yang diketahui (`0` untuk spin-down bebas)
+//! dan `I_tambahan` massa kalibrasi yang diketahui inertia-nya.
+//! spin-down bebas saja tidak cukup, perlu minimal satu run dengan referensi
+//! (massa kalibrasi atau torsi yang diketahui).
+use std::ops::Range;
+
+use chrono::{NaiveDateTime, Utc};
+
+use crate::{
+    convertions::prelude::*, BufferData, DynoConfig, DynoErr, DynoResult, Float, InertiaComponent,
+    InertiaModel, InertiaShape,
+};
+
+/// satu rekaman yang digunakan untuk kalibrasi
+#[derive(Debug, Clone)]
+pub struct CalibrationRun<'a> {
+    pub buffer: &'a BufferData,
+    pub range: Range<usize>,
+    /// torsi referensi yang bekerja pada roller selama run
+    pub applied_torque: NewtonMeter,
+    /// inertia massa kalibrasi yang dipasang selama run (kg.m²)
+    pub added_inertia: Float,
+}
+
+impl<'a> CalibrationRun<'a> {
+    /// ## free spin-down of the whole buffer
+    pub fn spin_down(buffer: &'a BufferData) -> Self {
+        Self {
+            buffer,
+            range: 0..buffer.len(),
+            applied_torque: NewtonMeter::default(),
+            added_inertia: 0.0,
+        }
+    }
+
+    /// ## spin-down with a known calibration mass mounted on the roller
+    pub fn with_added_inertia(buffer: &'a BufferData, added_inertia: Float) -> Self {
+        Self {
+            added_inertia,
+            ..Self::spin_down(buffer)
+        }
+    }
+
+    /// ## run with a known reference torque on the roller
+    pub fn with_torque(buffer: &'a BufferData, applied_torque: NewtonMeter) -> Self {
+        Self {
+            applied_torque,
+            ..Self::spin_down(buffer)
+        }
+    }
+
+    #[inline]
+    pub fn range(self, range: Range<usize>) -> Self {
+        Self { range, ..self }
+    }
+
+    #[inline]
+    fn has_reference(&self) -> bool {
+        self.applied_torque.value() != 0.0 || self.added_inertia != 0.0
+    }
+
+    /// baris least-squares `([α, 1, ω], T_ref - I_tambahan * α)`,
+    /// ω dan α tanpa lag filter realtime, lihat [`BufferData::roller_motion`]
+    fn rows(&self, config: &DynoConfig) -> Vec<([Float; 3], Float)> {
+        let (omega, alpha) = self.buffer.roller_motion(config, self.range.clone());
+        omega
+            .into_iter()
+            .zip(alpha)
+            .filter(|(omega, _)| *omega > 0.0)
+            .map(|(omega, alpha)| {
+                (
+                    [alpha, 1.0, omega],
+                    self.applied_torque.value() - self.added_inertia * alpha,
+                )
+            })
+            .collect()
+    }
+}
+
+/// hasil kalibrasi inertia dan gesekan roller
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CalibrationResult {
+    /// inertia efektif pada poros roller (kg.m²)
+    pub inertia: Float,
+    /// gesekan coulomb (Nm)
+    pub friction_coulomb: Float,
+    /// gesekan viscous (Nm.s/rad)
+    pub friction_viscous: Float,
+    /// koefisien determinasi hasil fitting (0.0 - 1.0)
+    pub r_squared: Float,
+    /// root mean square error torsi hasil fitting (Nm)
+    pub rmse: Float,
+    pub samples: usize,
+    pub time_stamp: NaiveDateTime,
+}
+
+impl CalibrationResult {
+    /// ## fit inertia and friction from the calibration `runs`
+    /// `config` adalah config saat run direkam (encoder dan estimator turunan)
+    pub fn fit(config: &DynoConfig, runs: &[CalibrationRun<'_>]) -> DynoResult<Self> {
+        if !runs.iter().any(CalibrationRun::has_reference) {
+            return Err(DynoErr::validation_error(
+                "Calibration needs at least one run with known added inertia or reference torque",
+            ));
+        }
+        let fit = super::least_squares(runs.iter().flat_map(|run| run.rows(config))).ok_or_else(
+            || DynoErr::validation_error("Not enough calibration samples to fit the inertia"),
+        )?;
+        let [inertia, friction_coulomb, friction_viscous] = fit.coefficients;
+        if !inertia.is_normal() || inertia <= 0.0 {
+            return Err(DynoErr::validation_error(format!(
+                "Calibration result is not valid, inertia: {inertia}"
+            )));
+        }
+        Ok(Self {
+            inertia,
+            friction_coulomb,
+            friction_viscous,
+            r_squared: fit.r_squared,
+            rmse: fit.rmse,
+            samples: fit.samples,
+            time_stamp: Utc::now().naive_local(),
+        })
+    }
+
+    /// ## write the calibrated inertia back into the config
+    /// mengganti `config.inertia` dengan satu komponen hasil pengukuran
+    pub fn apply_to(&self, config: &mut DynoConfig) {
+        config.inertia = InertiaModel {
+            components: vec![InertiaComponent {
+                name: "Kalibrasi".to_owned(),
+                shape: InertiaShape::Measured {
+                    moment: self.inertia,
+                },
+                ratio: 1.0,
+            }],
+        };
+        config.calibration = Some(*self);
+    }
+}
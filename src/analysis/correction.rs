@@ -0,0 +1,158 @@
+//! # Atmospheric Correction
+//! faktor koreksi daya terhadap kondisi udara saat pengujian, agar hasil dyno dari hari/tempat
+//! yang berbeda dapat dibandingkan. nilai terkoreksi = nilai terukur * faktor koreksi.
+use crate::{convertions::prelude::*, Float};
+
+/// kondisi udara sekitar saat pengujian
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AmbientConditions {
+    /// suhu udara masuk (intake)
+    pub temperature: Celcius,
+    /// tekanan udara total (barometrik)
+    pub pressure: KiloPascal,
+    /// kelembapan relatif (0 - 100 %)
+    pub humidity: Float,
+}
+
+impl Default for AmbientConditions {
+    /// kondisi referensi SAE J1349, 25 °C dan 99 kPa udara kering
+    fn default() -> Self {
+        Self {
+            temperature: Celcius(25.0),
+            pressure: KiloPascal(99.0),
+            humidity: 0.0,
+        }
+    }
+}
+
+impl AmbientConditions {
+    /// ## saturation vapor pressure of water (Arden Buck equation)
+    pub fn saturation_vapor_pressure(&self) -> KiloPascal {
+        let t = self.temperature.value();
+        KiloPascal(0.61121 * ((18.678 - t / 234.5) * (t / (257.14 + t))).exp())
+    }
+
+    /// ## partial pressure of water vapor in the air
+    #[inline]
+    pub fn vapor_pressure(&self) -> KiloPascal {
+        self.saturation_vapor_pressure() * (self.humidity.clamp(0.0, 100.0) / 100.0)
+    }
+
+    /// ## dry air pressure, total pressure minus vapor pressure
+    #[inline]
+    pub fn dry_pressure(&self) -> KiloPascal {
+        self.pressure - self.vapor_pressure()
+    }
+
+    #[inline]
+    fn temperature_kelvin(&self) -> Float {
+        self.temperature.value() + 273.15
+    }
+}
+
+/// standar koreksi yang digunakan
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    derive_more::Display,
+)]
+pub enum CorrectionStandard {
+    /// tanpa koreksi, faktor selalu `1.0`
+    #[default]
+    #[display(fmt = "Uncorrected")]
+    None,
+    /// SAE J1349, referensi 25 °C dan 99 kPa udara kering
+    #[display(fmt = "SAE J1349")]
+    SaeJ1349,
+    /// DIN 70020, referensi 20 °C dan 1013 mbar tekanan total
+    #[display(fmt = "DIN 70020")]
+    Din70020,
+    /// EEC 80/1269, referensi 25 °C dan 99 kPa udara kering
+    #[display(fmt = "EEC 80/1269")]
+    Eec80_1269,
+}
+
+impl CorrectionStandard {
+    pub fn into_iter() -> impl Iterator<Item = Self> {
+        [Self::None, Self::SaeJ1349, Self::Din70020, Self::Eec80_1269].into_iter()
+    }
+
+    /// ## correction factor for the given ambient conditions
+    /// return `1.0` if the conditions are not valid (non positive pressure or temperature)
+    /// ```
+    /// use dyno_core::{AmbientConditions, CorrectionStandard};
+    /// let cf = CorrectionStandard::SaeJ1349.factor(&AmbientConditions::default());
+    /// assert!((cf - 1.0).abs() < 1e-3);
+    /// ```
+    pub fn factor(self, ambient: &AmbientConditions) -> Float {
+        let kelvin = ambient.temperature_kelvin();
+        let dry = ambient.dry_pressure().value();
+        let total = ambient.pressure.value();
+        if kelvin <= 0.0 || dry <= 0.0 || total <= 0.0 {
+            return 1.0;
+        }
+        let factor = match self {
+            Self::None => 1.0,
+            Self::SaeJ1349 => 1.18 * (99.0 / dry) * (kelvin / 298.15).sqrt() - 0.18,
+            Self::Din70020 => (101.3 / total) * (kelvin / 293.15).sqrt(),
+            Self::Eec80_1269 => (99.0 / dry).powf(1.2) * (kelvin / 298.15).powf(0.6),
+        };
+        crate::ternary!((factor.is_normal() && factor > 0.0)?(factor): (1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Float, b: Float) -> bool {
+        (a - b).abs() < 1e-3
+    }
+
+    #[test]
+    fn test_reference_conditions() {
+        let sae = AmbientConditions::default();
+        assert!(close(CorrectionStandard::SaeJ1349.factor(&sae), 1.0));
+        assert!(close(CorrectionStandard::Eec80_1269.factor(&sae), 1.0));
+
+        let din = AmbientConditions {
+            temperature: Celcius(20.0),
+            pressure: HectoPascal(1013.0).to_kilo_pascal(),
+            humidity: 80.0,
+        };
+        assert!(close(CorrectionStandard::Din70020.factor(&din), 1.0));
+        assert_eq!(CorrectionStandard::None.factor(&din), 1.0);
+    }
+
+    #[test]
+    fn test_hot_humid_day() {
+        let ambient = AmbientConditions {
+            temperature: Celcius(35.0),
+            pressure: KiloPascal(100.0),
+            humidity: 50.0,
+        };
+        assert!(close(ambient.vapor_pressure().value(), 2.813));
+        assert!(close(CorrectionStandard::SaeJ1349.factor(&ambient), 1.0420));
+        assert!(close(CorrectionStandard::Din70020.factor(&ambient), 1.0386));
+        assert!(close(
+            CorrectionStandard::Eec80_1269.factor(&ambient),
+            1.0429
+        ));
+    }
+
+    #[test]
+    fn test_invalid_conditions() {
+        let ambient = AmbientConditions {
+            pressure: KiloPascal(0.0),
+            ..Default::default()
+        };
+        assert_eq!(CorrectionStandard::SaeJ1349.factor(&ambient), 1.0);
+    }
+}
@@ -0,0 +1,212 @@
+//! # Power Curve
+//! kurva torsi dan horsepower terhadap rpm, hasil binning sample dari satu pull.
+use std::ops::Range;
+
+use crate::{convertions::prelude::*, BufferData, Float, Numeric, PullSegment};
+
+/// sumber rpm yang digunakan sebagai sumbu x kurva
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    derive_more::Display,
+)]
+pub enum RpmSource {
+    #[default]
+    Engine,
+    Roda,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CurvePoint {
+    /// titik tengah bin rpm
+    pub rpm: RotationPerMinute,
+    pub torque: NewtonMeter,
+    pub horsepower: HorsePower,
+    /// jumlah sample dalam bin, `0` jika hasil interpolasi
+    pub samples: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct PowerCurve {
+    pub bin_size: RotationPerMinute,
+    pub source: RpmSource,
+    pub points: Vec<CurvePoint>,
+}
+
+impl PowerCurve {
+    pub const DEFAULT_BIN_SIZE: RotationPerMinute = RotationPerMinute(100.0);
+
+    /// ## build the curve from sample `range` of the buffer
+    /// setiap sample dimasukkan ke bin `round(rpm / bin_size)`, nilai dalam bin dirata-rata,
+    /// bin kosong di antara bin yang terisi diisi dengan interpolasi linear.
+    /// kolom `*_smooth` digunakan jika sudah dihitung dengan [`BufferData::smooth`].
+    pub fn from_range(
+        buffer: &BufferData,
+        range: Range<usize>,
+        bin_size: RotationPerMinute,
+        source: RpmSource,
+    ) -> Self {
+        let bin_size =
+            crate::ternary!((bin_size.value() > 0.0)?(bin_size): (Self::DEFAULT_BIN_SIZE));
+        let (rpm, torque, horsepower) = match (buffer.is_smoothed(), source) {
+            (true, RpmSource::Engine) => (
+                &buffer.rpm_engine_smooth,
+                &buffer.torque_smooth,
+                &buffer.horsepower_smooth,
+            ),
+            (true, RpmSource::Roda) => (
+                &buffer.rpm_roda_smooth,
+                &buffer.torque_smooth,
+                &buffer.horsepower_smooth,
+            ),
+            (false, RpmSource::Engine) => (&buffer.rpm_engine, &buffer.torque, &buffer.horsepower),
+            (false, RpmSource::Roda) => (&buffer.rpm_roda, &buffer.torque, &buffer.horsepower),
+        };
+        let end = range
+            .end
+            .min(rpm.len())
+            .min(torque.len())
+            .min(horsepower.len());
+        let start = range.start.min(end);
+
+        // (bin, sum torque, sum horsepower, count)
+        let mut bins: Vec<(i64, Float, Float, usize)> = Vec::new();
+        for idx in start..end {
+            let value = rpm[idx].value();
+            if !value.is_normal() || value < 0.0 {
+                continue;
+            }
+            let bin = (value / bin_size.value()).round() as i64;
+            let (torque, hp) = (torque[idx].value(), horsepower[idx].value());
+            match bins.binary_search_by_key(&bin, |b| b.0) {
+                Ok(pos) => {
+                    let b = &mut bins[pos];
+                    b.1 += torque;
+                    b.2 += hp;
+                    b.3 += 1;
+                }
+                Err(pos) => bins.insert(pos, (bin, torque, hp, 1)),
+            }
+        }
+
+        let mut points: Vec<CurvePoint> = Vec::with_capacity(bins.len());
+        for (bin, torque, hp, count) in bins {
+            let point = CurvePoint {
+                rpm: bin_size * bin as Float,
+                torque: NewtonMeter(torque / count as Float),
+                horsepower: HorsePower(hp / count as Float),
+                samples: count,
+            };
+            if let Some(prev) = points.last().copied() {
+                let prev_bin = (prev.rpm.value() / bin_size.value()).round() as i64;
+                let gap = bin - prev_bin;
+                for step in 1..gap {
+                    let t = step as Float / gap as Float;
+                    points.push(CurvePoint {
+                        rpm: bin_size * (prev_bin + step) as Float,
+                        torque: prev.torque + (point.torque - prev.torque) * t,
+                        horsepower: prev.horsepower + (point.horsepower - prev.horsepower) * t,
+                        samples: 0,
+                    });
+                }
+            }
+            points.push(point);
+        }
+
+        Self {
+            bin_size,
+            source,
+            points,
+        }
+    }
+
+    /// ## build the curve from a detected pull
+    /// ```
+    /// use dyno_core::{simulator::DynoSimulator, *};
+    /// let mut config = DynoConfig::default();
+    /// let mut buffer = BufferData::new();
+    /// for serial in DynoSimulator::from_config(&config) {
+    ///     buffer.push_from_serial(&mut config, serial);
+    /// }
+    /// let pull = buffer.best_pull(&PullConfig { min_duration_ms: 0, ..Default::default() }).unwrap();
+    /// let curve = PowerCurve::from_pull(&buffer, &pull, PowerCurve::DEFAULT_BIN_SIZE, RpmSource::Engine);
+    /// assert!(curve.peak_horsepower().is_some());
+    /// ```
+    #[inline]
+    pub fn from_pull(
+        buffer: &BufferData,
+        pull: &PullSegment,
+        bin_size: RotationPerMinute,
+        source: RpmSource,
+    ) -> Self {
+        Self::from_range(buffer, pull.range(), bin_size, source)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// peak horsepower beserta rpm-nya
+    pub fn peak_horsepower(&self) -> Option<CurvePoint> {
+        self.points.iter().copied().max_by(|a, b| {
+            a.horsepower
+                .partial_cmp(&b.horsepower)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// peak torque beserta rpm-nya
+    pub fn peak_torque(&self) -> Option<CurvePoint> {
+        self.points.iter().copied().max_by(|a, b| {
+            a.torque
+                .partial_cmp(&b.torque)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// ## linear interpolation of the curve at `rpm`
+    /// return `None` if `rpm` is outside of the curve
+    pub fn at_rpm(&self, rpm: RotationPerMinute) -> Option<CurvePoint> {
+        let pos = self.points.partition_point(|p| p.rpm < rpm);
+        let upper = self.points.get(pos)?;
+        if upper.rpm == rpm {
+            return Some(*upper);
+        }
+        let lower = self.points.get(pos.checked_sub(1)?)?;
+        let t = ((rpm - lower.rpm) / (upper.rpm - lower.rpm)).value();
+        Some(CurvePoint {
+            rpm,
+            torque: lower.torque + (upper.torque - lower.torque) * t,
+            horsepower: lower.horsepower + (upper.horsepower - lower.horsepower) * t,
+            samples: 0,
+        })
+    }
+
+    #[inline]
+    pub fn torque_points<Out: FromIterator<[f64; 2]>>(&self) -> Out {
+        self.points
+            .iter()
+            .map(|p| [p.rpm.to_f64(), p.torque.to_f64()])
+            .collect()
+    }
+
+    #[inline]
+    pub fn horsepower_points<Out: FromIterator<[f64; 2]>>(&self) -> Out {
+        self.points
+            .iter()
+            .map(|p| [p.rpm.to_f64(), p.horsepower.to_f64()])
+            .collect()
+    }
+}
@@ -0,0 +1,142 @@
+//! # Drivetrain Loss
+//! estimasi rugi-rugi gesek (drivetrain, ban, bearing roller) dari fase coastdown setelah pull.
+//! saat throttle dilepas roller melambat hanya karena rugi-rugi, sehingga daya perlambatan
+//! roller sama dengan daya yang hilang pada kecepatan tersebut.
+//! daya engine (crank) diestimasi dari `daya roda + rugi-rugi`.
+use std::ops::Range;
+
+use crate::{
+    convertions::prelude::*, Buffer, BufferData, DynoConfig, Float, PullConfig, PullSegment,
+};
+
+/// # Drivetrain loss model
+/// rugi-rugi daya sebagai polinomial kecepatan roller,
+/// `loss(v) = c0 + c1 * v + c2 * v^2` dengan `v` dalam km/h dan hasil dalam [`HorsePower`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DrivetrainLoss {
+    pub coefficients: [Float; 3],
+    /// jumlah sample coastdown yang digunakan saat fitting
+    pub samples: usize,
+    /// koefisien determinasi hasil fitting (0.0 - 1.0)
+    pub r_squared: Float,
+}
+
+impl DrivetrainLoss {
+    /// ## estimated power loss at roller `speed`
+    pub fn loss_at(&self, speed: KilometresPerHour) -> HorsePower {
+        let v = speed.value();
+        if !v.is_normal() || v < 0.0 {
+            return HorsePower::default();
+        }
+        let [c0, c1, c2] = self.coefficients;
+        HorsePower((c0 + c1 * v + c2 * v * v).max(0.0))
+    }
+
+    /// ## estimated crank power from wheel power at roller `speed`
+    #[inline]
+    pub fn crank_horsepower(&self, wheel: HorsePower, speed: KilometresPerHour) -> HorsePower {
+        wheel + self.loss_at(speed)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples == 0
+    }
+
+    /// ## least-squares fit of the loss polynomial from `(speed, loss)` points
+    /// return `None` if there is not enough points to fit
+    pub fn fit(points: impl IntoIterator<Item = (KilometresPerHour, HorsePower)>) -> Option<Self> {
+        let rows = points.into_iter().map(|(speed, loss)| {
+            let v = speed.value();
+            ([1.0, v, v * v], loss.value())
+        });
+        let fit = super::least_squares(rows)?;
+        Some(Self {
+            coefficients: fit.coefficients,
+            samples: fit.samples,
+            r_squared: fit.r_squared,
+        })
+    }
+}
+
+impl BufferData {
+    /// ## find the coastdown phase after the `pull`
+    /// dimulai dari puncak pull, berakhir saat kecepatan di bawah `config.min_speed`
+    /// atau rpm roller naik kembali (pull berikutnya).
+    pub fn detect_coastdown(
+        &self,
+        pull: &PullSegment,
+        config: &PullConfig,
+    ) -> Option<Range<usize>> {
+        let len = self.rpm_roda.len().min(self.speed.len());
+        let start = pull.end.checked_sub(1)?;
+        if start >= len {
+            return None;
+        }
+        let mut low = self.rpm_roda[start];
+        let mut end = start + 1;
+        while end < len && self.speed[end] >= config.min_speed {
+            let rpm = self.rpm_roda[end];
+            if rpm > low * (1.0 + config.tolerance) {
+                break;
+            }
+            if rpm < low {
+                low = rpm;
+            }
+            end += 1;
+        }
+        crate::ternary!((end - start > 2)?(Some(start..end)): (None))
+    }
+
+    /// ## estimate drivetrain loss from coastdown sample `range`
+    /// daya rugi-rugi setiap sample dihitung dari perlambatan roller (rad/s²)
+    /// tanpa lag filter realtime (lihat [`BufferData::roller_motion`]),
+    /// dengan inertia efektif yang sama seperti [`crate::Data::from_serial`].
+    pub fn estimate_drivetrain_loss(
+        &self,
+        config: &DynoConfig,
+        range: Range<usize>,
+    ) -> Option<DrivetrainLoss> {
+        let end = range.end.min(self.speed.len());
+        let start = range.start.min(end);
+        let (omega, alpha) = self.roller_motion(config, start..end);
+        let points = omega
+            .into_iter()
+            .zip(alpha)
+            .zip(&self.speed[start..])
+            .filter_map(|((omega, alpha), speed)| {
+                // abaikan sample yang tidak melambat (noise / nilai tertahan)
+                if alpha >= 0.0 {
+                    return None;
+                }
+                let torque = config.roller_torque(-alpha);
+                let rpm = RadiansPerSecond(omega).to_rotation_per_minute();
+                Some((*speed, HorsePower::from_nm(torque, rpm)))
+            });
+        DrivetrainLoss::fit(points)
+    }
+
+    /// ## detect the best pull and estimate drivetrain loss from its coastdown
+    pub fn estimate_drivetrain_loss_auto(
+        &self,
+        config: &DynoConfig,
+        pull_config: &PullConfig,
+    ) -> Option<DrivetrainLoss> {
+        let pull = self.best_pull(pull_config)?;
+        let range = self.detect_coastdown(&pull, pull_config)?;
+        self.estimate_drivetrain_loss(config, range)
+    }
+
+    /// ## estimated crank horsepower of every sample
+    pub fn crank_horsepower(&self, loss: &DrivetrainLoss) -> Buffer<HorsePower> {
+        let mut crank = Buffer::new_buf(self.horsepower.len());
+        crank.extend(
+            self.horsepower
+                .iter()
+                .zip(self.speed.iter())
+                .map(|(hp, speed)| loss.crank_horsepower(*hp, *speed)),
+        );
+        crank
+    }
+}
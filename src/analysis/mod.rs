@@ -0,0 +1,97 @@
+pub mod calibration;
+pub mod correction;
+pub mod curve;
+pub mod loss;
+pub mod pull;
+
+pub mod prelude {
+    pub use super::calibration::*;
+    pub use super::correction::*;
+    pub use super::curve::*;
+    pub use super::loss::*;
+    pub use super::pull::*;
+}
+
+use crate::Float;
+
+/// hasil fitting linear least-squares `y = x · coefficients`
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LeastSquares<const N: usize> {
+    pub coefficients: [Float; N],
+    pub r_squared: Float,
+    pub rmse: Float,
+    pub samples: usize,
+}
+
+/// ## linear least-squares fit using the normal equation
+/// return `None` if there is not enough rows or the system is singular
+pub(crate) fn least_squares<const N: usize>(
+    rows: impl IntoIterator<Item = ([Float; N], Float)>,
+) -> Option<LeastSquares<N>> {
+    // normal equation, A^T A x = A^T y
+    let mut ata = [[0.0 as Float; N]; N];
+    let mut aty = [0.0 as Float; N];
+    let rows = rows
+        .into_iter()
+        .filter(|(x, y)| y.is_finite() && x.iter().all(|v| v.is_finite()))
+        .collect::<Vec<_>>();
+    if rows.len() < N {
+        return None;
+    }
+    for (x, y) in &rows {
+        for i in 0..N {
+            for j in 0..N {
+                ata[i][j] += x[i] * x[j];
+            }
+            aty[i] += x[i] * y;
+        }
+    }
+    let coefficients = solve(ata, aty)?;
+
+    let len = rows.len() as Float;
+    let mean = rows.iter().map(|(_, y)| y).sum::<Float>() / len;
+    let (ss_res, ss_tot) = rows.iter().fold((0.0, 0.0), |(res, tot), (x, y)| {
+        let fit = x
+            .iter()
+            .zip(coefficients)
+            .map(|(a, c)| a * c)
+            .sum::<Float>();
+        (res + (y - fit).powi(2), tot + (y - mean).powi(2))
+    });
+    Some(LeastSquares {
+        coefficients,
+        r_squared: crate::ternary!((ss_tot > 0.0)?(1.0 - ss_res / ss_tot): (1.0)),
+        rmse: (ss_res / len).sqrt(),
+        samples: rows.len(),
+    })
+}
+
+/// gaussian elimination with partial pivot
+fn solve<const N: usize>(mut a: [[Float; N]; N], mut b: [Float; N]) -> Option<[Float; N]> {
+    for col in 0..N {
+        let pivot = (col..N).max_by(|&x, &y| {
+            a[x][col]
+                .abs()
+                .partial_cmp(&a[y][col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if a[pivot][col].abs() < Float::EPSILON {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..N {
+            let (f, pivot_row) = (a[row][col] / a[col][col], a[col]);
+            for (dst, src) in a[row].iter_mut().zip(pivot_row).skip(col) {
+                *dst -= f * src;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = [0.0; N];
+    for row in (0..N).rev() {
+        let sum = (row + 1..N).map(|k| a[row][k] * x[k]).sum::<Float>();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
@@ -0,0 +1,182 @@
+//! # Pull Segmentation
+//! mendeteksi setiap pull (akselerasi dengan throttle penuh) dalam satu rekaman [`BufferData`].
+//! satu pull adalah kenaikan `rpm_roda` yang (hampir) monoton di atas kecepatan minimal,
+//! penurunan singkat karena noise atau perpindahan gigi masih ditoleransi.
+use std::ops::Range;
+
+use crate::{convertions::prelude::*, BufferData, Float, Numeric};
+
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PullConfig {
+    /// kecepatan minimal agar sample dianggap bagian dari pull
+    pub min_speed: KilometresPerHour,
+    /// kenaikan rpm roda minimal dari awal sampai puncak pull
+    pub min_rpm_rise: RotationPerMinute,
+    /// durasi minimal pull (ms)
+    pub min_duration_ms: i64,
+    /// toleransi penurunan rpm dari puncak sementara (0.0 - 1.0)
+    pub tolerance: Float,
+    /// jumlah sample berturut-turut di bawah toleransi sebelum pull dianggap selesai
+    pub max_dip_samples: usize,
+}
+
+impl Default for PullConfig {
+    fn default() -> Self {
+        Self {
+            min_speed: KilometresPerHour(10.0),
+            min_rpm_rise: RotationPerMinute(500.0),
+            min_duration_ms: 1_000,
+            tolerance: 0.03,
+            max_dip_samples: 5,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct PullSegment {
+    /// index sample pertama pull
+    pub start: usize,
+    /// index setelah sample terakhir pull (exclusive)
+    pub end: usize,
+    pub duration_ms: i64,
+
+    pub start_rpm: RotationPerMinute,
+    pub peak_rpm: RotationPerMinute,
+    pub peak_rpm_engine: RotationPerMinute,
+    pub peak_speed: KilometresPerHour,
+
+    pub peak_torque: NewtonMeter,
+    pub peak_torque_index: usize,
+    pub peak_horsepower: HorsePower,
+    pub peak_horsepower_index: usize,
+}
+
+impl PullSegment {
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    fn from_range(buffer: &BufferData, range: Range<usize>) -> Self {
+        fn peak<T: Numeric>(values: &[T], offset: usize) -> (usize, T) {
+            values.iter().enumerate().fold(
+                (offset, T::default()),
+                |(idx, max), (i, v)| crate::ternary!((*v > max)?((offset + i, *v)): ((idx, max))),
+            )
+        }
+        let Range { start, end } = range;
+        let (peak_torque_index, peak_torque) = peak(&buffer.torque[start..end], start);
+        let (peak_horsepower_index, peak_horsepower) = peak(&buffer.horsepower[start..end], start);
+        Self {
+            start,
+            end,
+            duration_ms: buffer.time_stamp[end - 1] - buffer.time_stamp[start],
+            start_rpm: buffer.rpm_roda[start],
+            peak_rpm: peak(&buffer.rpm_roda[start..end], start).1,
+            peak_rpm_engine: peak(&buffer.rpm_engine[start..end], start).1,
+            peak_speed: peak(&buffer.speed[start..end], start).1,
+            peak_torque,
+            peak_torque_index,
+            peak_horsepower,
+            peak_horsepower_index,
+        }
+    }
+}
+
+impl BufferData {
+    /// ## detect every acceleration pull in this recording
+    /// ```
+    /// use dyno_core::{simulator::DynoSimulator, BufferData, DynoConfig, PullConfig};
+    /// let mut config = DynoConfig::default();
+    /// let mut buffer = BufferData::new();
+    /// for serial in DynoSimulator::from_config(&config) {
+    ///     buffer.push_from_serial(&mut config, serial);
+    /// }
+    /// let pulls = buffer.detect_pulls(&PullConfig { min_duration_ms: 0, ..Default::default() });
+    /// assert_eq!(pulls.len(), 1);
+    /// ```
+    pub fn detect_pulls(&self, config: &PullConfig) -> Vec<PullSegment> {
+        let len = self
+            .rpm_roda
+            .len()
+            .min(self.speed.len())
+            .min(self.time_stamp.len());
+        let mut pulls = Vec::new();
+        let mut active: Option<(usize, usize)> = None; // (start, index puncak rpm)
+        let mut dip = 0usize;
+
+        let close = |start: usize, peak: usize, pulls: &mut Vec<PullSegment>| {
+            let segment = PullSegment::from_range(self, start..peak + 1);
+            if segment.peak_rpm - segment.start_rpm >= config.min_rpm_rise
+                && segment.duration_ms >= config.min_duration_ms
+            {
+                pulls.push(segment);
+            }
+        };
+
+        for idx in 0..len {
+            let rpm = self.rpm_roda[idx];
+            if self.speed[idx] < config.min_speed {
+                if let Some((start, peak)) = active.take() {
+                    close(start, peak, &mut pulls);
+                }
+                continue;
+            }
+            let Some((start, peak)) = active.as_mut() else {
+                active = Some((idx, idx));
+                dip = 0;
+                continue;
+            };
+            let peak_rpm = self.rpm_roda[*peak];
+            let rising = peak_rpm - self.rpm_roda[*start] >= config.min_rpm_rise;
+            if !rising && rpm <= self.rpm_roda[*start] {
+                // belum ada kenaikan berarti, geser awal pull ke titik terendah
+                (*start, *peak) = (idx, idx);
+                dip = 0;
+                continue;
+            }
+            if rpm > peak_rpm {
+                *peak = idx;
+                dip = 0;
+            } else if rpm < peak_rpm * (1.0 - config.tolerance) {
+                dip += 1;
+            } else {
+                // rpm datar di sekitar puncak (cruising), belum dianggap pull
+                if !rising && idx - *peak > config.max_dip_samples {
+                    (*start, *peak) = (idx, idx);
+                }
+                dip = 0;
+            }
+
+            if dip > config.max_dip_samples {
+                let (start, peak) = (*start, *peak);
+                close(start, peak, &mut pulls);
+                active = None;
+            }
+        }
+        if let Some((start, peak)) = active {
+            close(start, peak, &mut pulls);
+        }
+        pulls
+    }
+
+    /// ## the pull with the highest peak horsepower
+    pub fn best_pull(&self, config: &PullConfig) -> Option<PullSegment> {
+        self.detect_pulls(config).into_iter().max_by(|a, b| {
+            a.peak_horsepower
+                .partial_cmp(&b.peak_horsepower)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+}
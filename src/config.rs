@@ -1,6 +1,16 @@
 use crate::{
-    convertions::{length, weight},
-    data_structure::filter::DataFilter,
+    analysis::{
+        calibration::CalibrationResult,
+        correction::{AmbientConditions, CorrectionStandard},
+        loss::DrivetrainLoss,
+    },
+    convertions::{length, torque, weight},
+    data_structure::{
+        derivative::DerivativeEstimator,
+        filter::{DataFilter, FilterSnapshot},
+        quality::{EncoderConfig, QualityConfig},
+        session::SessionConfig,
+    },
     MotorInfo, MotorType, Numeric,
 };
 
@@ -42,6 +52,34 @@ pub struct DynoConfig {
 
     #[serde(default)]
     pub filter: DataFilter,
+
+    /// estimator percepatan sudut roller untuk perhitungan torsi
+    #[serde(default)]
+    pub derivative: DerivativeEstimator,
+
+    #[serde(default)]
+    pub session: SessionConfig,
+
+    #[serde(default)]
+    pub encoder: EncoderConfig,
+    #[serde(default)]
+    pub quality: QualityConfig,
+
+    /// model inertia rig, jika kosong dihitung dari `berat_beban` dan `diameter_roller_beban`
+    #[serde(default)]
+    pub inertia: InertiaModel,
+    /// hasil kalibrasi inertia terakhir, lihat [`CalibrationResult::apply_to`]
+    #[serde(default)]
+    pub calibration: Option<CalibrationResult>,
+
+    #[serde(default)]
+    pub ambient: AmbientConditions,
+    #[serde(default)]
+    pub correction: CorrectionStandard,
+
+    /// model rugi-rugi drivetrain hasil coastdown, digunakan ulang untuk motor yang sama
+    #[serde(default)]
+    pub drivetrain_loss: DrivetrainLoss,
 }
 
 impl Default for DynoConfig {
@@ -60,6 +98,15 @@ impl Default for DynoConfig {
             motor_type: MotorType::default(),
             motor_info: MotorInfo::default(),
             filter: DataFilter::default(),
+            derivative: DerivativeEstimator::default(),
+            session: SessionConfig::default(),
+            encoder: EncoderConfig::default(),
+            quality: QualityConfig::default(),
+            inertia: InertiaModel::default(),
+            calibration: None,
+            ambient: AmbientConditions::default(),
+            correction: CorrectionStandard::default(),
+            drivetrain_loss: DrivetrainLoss::default(),
         }
     }
 }
@@ -67,6 +114,7 @@ impl Default for DynoConfig {
 impl DynoConfig {
     pub fn init(&mut self) {
         self.filter.reset();
+        self.derivative.reset();
         self.gaya_beban = self.berat_beban.value() * crate::GRAVITY_SPEED;
         self.keliling_roller = self.diameter_roller * crate::PI;
     }
@@ -79,7 +127,192 @@ impl DynoConfig {
     pub fn inertia_roller_beban(&self) -> crate::Float {
         0.5 * self.berat_beban.to_float() * self.diameter_roller_beban.to_float().powi(2)
     }
+
+    /// ## inertia model of the rig
+    /// return `self.inertia` if not empty, otherwise a single roller beban
+    /// dengan inertia legacy [`DynoConfig::legacy_inertia`]
+    pub fn inertia_model(&self) -> InertiaModel {
+        if !self.inertia.is_empty() {
+            return self.inertia.clone();
+        }
+        InertiaModel {
+            components: vec![InertiaComponent {
+                name: "Roller Beban".to_owned(),
+                shape: InertiaShape::Measured {
+                    moment: self.legacy_inertia(),
+                },
+                ratio: 1.0,
+            }],
+        }
+    }
+
+    /// inertia config lama tanpa `inertia` model, [`DynoConfig::inertia_roller_beban`] dikali
+    /// `perbandingan_gear` (linear) agar torsi sama dengan rekaman dan kalibrasi sebelumnya
+    #[inline]
+    pub fn legacy_inertia(&self) -> crate::Float {
+        self.inertia_roller_beban() * self.perbandingan_gear()
+    }
+
+    /// inertia efektif pada poros roller (kg.m²)
+    #[inline]
+    pub fn effective_inertia(&self) -> crate::Float {
+        if self.inertia.is_empty() {
+            self.legacy_inertia()
+        } else {
+            self.inertia.effective_inertia()
+        }
+    }
+
+    /// torsi pada roller dari percepatan sudut roller `alpha` (rad/s²)
+    #[inline]
+    pub fn roller_torque(&self, alpha: crate::Float) -> torque::NewtonMeter {
+        torque::NewtonMeter::new(self.effective_inertia() * alpha)
+    }
+
+    /// faktor koreksi atmosfer dari `ambient` sesuai standar `correction`
+    #[inline]
+    pub fn correction_factor(&self) -> crate::Float {
+        self.correction.factor(&self.ambient)
+    }
+
+    /// ## number of warm-up samples for the filter and the derivative estimator
+    /// dengan period sample `period_ms`, lihat [`crate::FilterKind::warmup_len`]
+    pub fn warmup_len(&self, period_ms: u32) -> usize {
+        Ord::max(
+            self.filter.warmup_len(period_ms as crate::Float * 0.001),
+            self.derivative.kind.window(),
+        )
+    }
+
+    /// ## state of the filter and the derivative estimator of the running session
+    #[inline]
+    pub fn snapshot(&self) -> FilterSnapshot {
+        FilterSnapshot::new(&self.filter, &self.derivative)
+    }
+
+    /// ## continue a session from `snapshot` without discontinuity in the filtered data
+    /// sample pertama setelah resume dianggap lanjutan langsung dari sample terakhir snapshot,
+    /// jeda selama sesi terhenti tidak ikut dihitung oleh filter dan estimator turunan
+    #[inline]
+    pub fn resume(&mut self, snapshot: FilterSnapshot) {
+        snapshot.restore(&mut self.filter, &mut self.derivative);
+    }
+
+    /// ## rebase the resumed state onto the time base of the sample at `time_ms`
+    /// `period_ms` adalah jarak sample ini dari sample terakhir snapshot,
+    /// tidak melakukan apa-apa jika tidak ada [`DynoConfig::resume`] yang tertunda
+    pub fn rebase_resumed(&mut self, time_ms: i64, period_ms: i64) {
+        let Some(last) = self.filter.take_resume() else {
+            return;
+        };
+        let offset = time_ms - period_ms - last;
+        self.filter.rebase(offset);
+        self.derivative.rebase(offset);
+    }
+}
+/// bentuk komponen yang berputar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InertiaShape {
+    /// silinder pejal, `0.5 * m * r^2`
+    SolidCylinder {
+        mass: weight::KiloGram,
+        diameter: length::Metres,
+    },
+    /// silinder berongga, `0.5 * m * (r_luar^2 + r_dalam^2)`
+    HollowCylinder {
+        mass: weight::KiloGram,
+        outer_diameter: length::Metres,
+        inner_diameter: length::Metres,
+    },
+    /// moment inertia hasil pengukuran / kalibrasi (kg.m²)
+    Measured { moment: crate::Float },
+}
+
+crate::macros::impl_serde_tagged!(InertiaShape {
+    SolidCylinder { mass: weight::KiloGram, diameter: length::Metres },
+    HollowCylinder { mass: weight::KiloGram, outer_diameter: length::Metres, inner_diameter: length::Metres },
+    Measured { moment: crate::Float },
+});
+
+impl InertiaShape {
+    /// moment inertia pada porosnya sendiri (kg.m²)
+    pub fn moment(&self) -> crate::Float {
+        match *self {
+            Self::SolidCylinder { mass, diameter } => {
+                0.5 * mass.to_float() * (diameter.to_float() * 0.5).powi(2)
+            }
+            Self::HollowCylinder {
+                mass,
+                outer_diameter,
+                inner_diameter,
+            } => {
+                0.5 * mass.to_float()
+                    * ((outer_diameter.to_float() * 0.5).powi(2)
+                        + (inner_diameter.to_float() * 0.5).powi(2))
+            }
+            Self::Measured { moment } => moment,
+        }
+    }
 }
+
+/// satu komponen berputar pada rig dyno
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct InertiaComponent {
+    pub name: String,
+    pub shape: InertiaShape,
+    /// perbandingan kecepatan sudut komponen terhadap roller (`ω_komponen / ω_roller`)
+    pub ratio: crate::Float,
+}
+
+impl InertiaComponent {
+    /// inertia komponen dilihat dari poros roller, `I * ratio^2`
+    #[inline]
+    pub fn effective_inertia(&self) -> crate::Float {
+        self.shape.moment() * self.ratio.powi(2)
+    }
+}
+
+/// # Inertia Model
+/// kumpulan komponen berputar (roller, flywheel, gear) pada rig dyno
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct InertiaModel {
+    pub components: Vec<InertiaComponent>,
+}
+
+impl InertiaModel {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    /// ## total effective inertia on the roller shaft (kg.m²)
+    /// ```
+    /// use dyno_core::{convertions::prelude::*, InertiaComponent, InertiaModel, InertiaShape};
+    /// let model = InertiaModel {
+    ///     components: vec![
+    ///         InertiaComponent {
+    ///             name: "Roller".to_owned(),
+    ///             shape: InertiaShape::SolidCylinder { mass: KiloGram(20.0), diameter: Metres(0.2) },
+    ///             ratio: 1.0,
+    ///         },
+    ///         InertiaComponent {
+    ///             name: "Flywheel".to_owned(),
+    ///             shape: InertiaShape::Measured { moment: 0.05 },
+    ///             ratio: 2.0,
+    ///         },
+    ///     ],
+    /// };
+    /// assert!((model.effective_inertia() - (0.1 + 0.2)).abs() < 1e-9);
+    /// ```
+    pub fn effective_inertia(&self) -> crate::Float {
+        self.components
+            .iter()
+            .map(InertiaComponent::effective_inertia)
+            .sum()
+    }
+}
+
 #[inline(always)]
 fn default_diameter_roller() -> length::Metres {
     length::Metres(0.1422) // 14.22 cm
@@ -1,6 +1,7 @@
 pub mod angular;
 pub mod length;
 pub mod power;
+pub mod pressure;
 pub mod speed;
 pub mod temperature;
 pub mod torque;
@@ -307,6 +308,7 @@ pub mod prelude {
     pub use super::angular::*;
     pub use super::length::*;
     pub use super::power::*;
+    pub use super::pressure::*;
     pub use super::speed::*;
     pub use super::temperature::*;
     pub use super::torque::*;
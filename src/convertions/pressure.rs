@@ -0,0 +1,28 @@
+pub trait Pressure: crate::Numeric {
+    fn to_pascal(self) -> Pascal {
+        Pascal::new(self)
+    }
+
+    fn to_hecto_pascal(self) -> HectoPascal {
+        HectoPascal::new(self)
+    }
+
+    fn to_kilo_pascal(self) -> KiloPascal {
+        KiloPascal::new(self)
+    }
+}
+
+super::declare_convertion_type!(Pressure => self {
+    Pascal["Pa"] [
+        to_hecto_pascal => HectoPascal  { self.0 * 0.01  },
+        to_kilo_pascal  => KiloPascal   { self.0 * 0.001 }
+    ],
+    HectoPascal["hPa"] [
+        to_pascal       => Pascal       { self.0 * 100.0 },
+        to_kilo_pascal  => KiloPascal   { self.0 * 0.1   }
+    ],
+    KiloPascal["kPa"] [
+        to_pascal       => Pascal       { self.0 * 1000.0 },
+        to_hecto_pascal => HectoPascal  { self.0 * 10.0   }
+    ]
+});
@@ -7,6 +7,11 @@ pub enum PointShowed {
     Half,
     Quarter,
     Num(usize),
+    /// seluruh sample diturunkan menjadi maksimal `n` point dengan LTTB, lihat [`lttb`]
+    Downsample(usize),
+    /// seluruh sample diturunkan menjadi maksimal `n` point min/max per bucket,
+    /// lihat [`min_max_decimate`]
+    MinMax(usize),
 }
 impl std::fmt::Display for PointShowed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -15,15 +20,18 @@ impl std::fmt::Display for PointShowed {
             PointShowed::Half => f.write_str("Half Points"),
             PointShowed::Quarter => f.write_str("Quarter Points"),
             PointShowed::Num(n) => write!(f, "{n} Points"),
+            PointShowed::Downsample(n) => write!(f, "{n} Points (LTTB)"),
+            PointShowed::MinMax(n) => write!(f, "{n} Points (Min/Max)"),
         }
     }
 }
 
 impl PointShowed {
+    /// index sample pertama yang ditampilkan
     #[inline]
     const fn showed_len(self, len: usize) -> usize {
         match self {
-            PointShowed::All => len,
+            PointShowed::All | PointShowed::Downsample(_) | PointShowed::MinMax(_) => 0,
             PointShowed::Half => len / 2,
             PointShowed::Quarter => len - (len / 4),
             PointShowed::Num(n) => crate::ternary!((n > len)?(len): (len - n)),
@@ -35,27 +43,303 @@ impl PointShowed {
     }
 }
 
-#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
-pub struct Buffer<T>(Vec<T>);
+/// ## Largest-Triangle-Three-Buckets downsampling of `points` into `threshold` points
+/// point pertama dan terakhir selalu dipertahankan, setiap bucket diwakili point yang membentuk
+/// segitiga terbesar dengan point terpilih sebelumnya dan rata-rata bucket berikutnya,
+/// sehingga puncak kurva tidak hilang
+pub fn lttb(points: &[[f64; 2]], threshold: usize) -> Vec<[f64; 2]> {
+    let len = points.len();
+    if threshold >= len {
+        return points.to_vec();
+    }
+    if threshold < 3 {
+        return endpoints(points, threshold);
+    }
+    let every = (len - 2) as f64 / (threshold - 2) as f64;
+    let bucket = |i: usize| {
+        let start = (i as f64 * every) as usize + 1;
+        let end = (((i + 1) as f64 * every) as usize + 1).min(len - 1);
+        start..end
+    };
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0]);
+    let mut selected = points[0];
+    for i in 0..threshold - 2 {
+        let next = bucket(i + 1);
+        let next = match next.is_empty() {
+            true => len - 1..len,
+            false => next,
+        };
+        let count = next.len() as f64;
+        let [avg_x, avg_y] = points[next]
+            .iter()
+            .fold([0.0, 0.0], |[x, y], p| [x + p[0] / count, y + p[1] / count]);
+        let [ax, ay] = selected;
+        let Some(point) = points[bucket(i)].iter().copied().max_by(|p, q| {
+            let area = |[x, y]: [f64; 2]| ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs();
+            area(*p).total_cmp(&area(*q))
+        }) else {
+            continue;
+        };
+        sampled.push(point);
+        selected = point;
+    }
+    sampled.push(points[len - 1]);
+    sampled
+}
+
+/// ## min/max per bucket decimation of `points` into maximal `threshold` points
+/// setiap bucket diwakili point minimum dan maksimum sesuai urutan sumbu x,
+/// `threshold` di bawah 3 sama seperti [`lttb`] (point pertama dan terakhir)
+pub fn min_max_decimate(points: &[[f64; 2]], threshold: usize) -> Vec<[f64; 2]> {
+    let len = points.len();
+    if threshold >= len {
+        return points.to_vec();
+    }
+    if threshold < 3 {
+        return endpoints(points, threshold);
+    }
+    let buckets = threshold / 2;
+    let size = len.div_ceil(buckets);
+    let mut sampled = Vec::with_capacity(buckets * 2);
+    for chunk in points.chunks(size) {
+        let cmp = |a: &(usize, &[f64; 2]), b: &(usize, &[f64; 2])| a.1[1].total_cmp(&b.1[1]);
+        let (Some((min, _)), Some((max, _))) = (
+            chunk.iter().enumerate().min_by(cmp),
+            chunk.iter().enumerate().max_by(cmp),
+        ) else {
+            continue;
+        };
+        match (min.min(max), min.max(max)) {
+            (a, b) if a == b => sampled.push(chunk[a]),
+            (a, b) => sampled.extend([chunk[a], chunk[b]]),
+        }
+    }
+    sampled
+}
+
+/// maksimal `threshold` point dari point pertama dan terakhir, untuk `threshold` di bawah 3
+fn endpoints(points: &[[f64; 2]], threshold: usize) -> Vec<[f64; 2]> {
+    [points.first(), points.last()]
+        .into_iter()
+        .flatten()
+        .take(threshold)
+        .copied()
+        .collect()
+}
+
+/// perlakuan sample lama yang keluar dari ring buffer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RingOverflow {
+    /// sample lama dibuang
+    Evict,
+    /// sample lama ditambahkan ke file (f64 little-endian), lihat [`Buffer::read_spill`]
+    Spill(std::path::PathBuf),
+}
+
+/// # Ring buffer mode
+/// [`Buffer`] hanya menyimpan `capacity` sample terakhir.
+/// sample lama dikeluarkan per blok `capacity / 8` agar push tetap O(1) amortized,
+/// sehingga jumlah sample yang tersimpan antara `capacity` dan `capacity + capacity / 8`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingConfig {
+    pub capacity: usize,
+    pub overflow: RingOverflow,
+}
+
+impl RingConfig {
+    #[inline]
+    pub(crate) fn slack(&self) -> usize {
+        Ord::max(self.capacity / 8, 1)
+    }
+}
+
+/// file tujuan spill, tetap terbuka selama ring buffer aktif.
+/// hasil clone membuka file yang sama kembali saat spill berikutnya
+#[derive(Debug, Default)]
+struct SpillFile {
+    path: Option<std::path::PathBuf>,
+    file: Option<std::fs::File>,
+}
+
+impl Clone for SpillFile {
+    fn clone(&self) -> Self {
+        Self::new(self.path.clone())
+    }
+}
+
+impl SpillFile {
+    #[inline]
+    fn new(path: Option<std::path::PathBuf>) -> Self {
+        Self { path, file: None }
+    }
+
+    fn write<T: Numeric>(&mut self, values: &[T]) {
+        use std::io::Write;
+        let Some(path) = &self.path else {
+            return;
+        };
+        let bytes = values
+            .iter()
+            .flat_map(|v| v.to_f64().to_le_bytes())
+            .collect::<Vec<u8>>();
+        let file = match &mut self.file {
+            Some(file) => Ok(file),
+            None => std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map(|file| self.file.insert(file)),
+        };
+        if let Err(err) = file.and_then(|file| file.write_all(&bytes)) {
+            log::error!("Failed to spill buffer into {}: {err}", path.display());
+        }
+    }
+}
+
+/// penjumlahan yang jenuh pada `T::MIN` / `T::MAX` untuk integer, bukan overflow
+#[inline]
+fn saturating_add<T: Numeric>(a: T, b: T) -> T {
+    let zero = T::default();
+    match T::INTEGRAL {
+        true if b > zero && a > T::MAX - b => T::MAX,
+        true if b < zero && a < T::MIN - b => T::MIN,
+        _ => a + b,
+    }
+}
+
+/// # Running statistics
+/// statistik yang diperbarui setiap push, variance dengan algoritma Welford
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BufferStats<T> {
+    pub count: usize,
+    /// jenuh pada `T::MIN` / `T::MAX` untuk integer
+    pub sum: T,
+    pub min: T,
+    pub max: T,
+    pub mean: Float,
+    /// jumlah kuadrat selisih terhadap mean
+    m2: Float,
+}
+
+impl<T: Numeric> BufferStats<T> {
+    pub fn from_values(values: &[T]) -> Self {
+        let mut stats = Self::default();
+        values.iter().for_each(|v| stats.push(*v));
+        stats
+    }
+
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else if value < self.min {
+            self.min = value;
+        } else if value > self.max {
+            self.max = value;
+        }
+        self.count += 1;
+        self.sum = saturating_add(self.sum, value);
+        let x = value.to_float();
+        let delta = x - self.mean;
+        self.mean += delta / self.count as Float;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    /// ## population variance
+    #[inline]
+    pub fn variance(&self) -> Float {
+        crate::ternary!((self.count > 0)?(self.m2 / self.count as Float): (0.0))
+    }
+
+    /// ## sample variance (n - 1)
+    #[inline]
+    pub fn sample_variance(&self) -> Float {
+        crate::ternary!((self.count > 1)?(self.m2 / (self.count - 1) as Float): (0.0))
+    }
+
+    /// ## population standard deviation
+    #[inline]
+    pub fn std_dev(&self) -> Float {
+        self.variance().sqrt()
+    }
+
+    /// ## root mean square, `sqrt(mean² + variance)`
+    #[inline]
+    pub fn rms(&self) -> Float {
+        (self.mean * self.mean + self.variance()).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(transparent)]
+pub struct Buffer<T> {
+    data: Vec<T>,
+    #[serde(skip)]
+    ring: Option<RingConfig>,
+    #[serde(skip)]
+    spill: SpillFile,
+    /// jumlah sample yang sudah dikeluarkan dari ring buffer
+    #[serde(skip)]
+    evicted: usize,
+    /// statistik `data`, `None` jika kosong atau setelah diubah melalui `DerefMut`
+    #[serde(skip)]
+    stats: Option<BufferStats<T>>,
+}
 
 impl<T: Numeric> std::ops::Deref for Buffer<T> {
     type Target = Vec<T>;
     #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.data
     }
 }
 impl<T: Numeric> std::ops::DerefMut for Buffer<T> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        self.stats = None;
+        &mut self.data
+    }
+}
+
+impl<T: Numeric> From<Vec<T>> for Buffer<T> {
+    #[inline]
+    fn from(data: Vec<T>) -> Self {
+        Self {
+            stats: Some(BufferStats::from_values(&data)),
+            data,
+            ring: None,
+            spill: SpillFile::default(),
+            evicted: 0,
+        }
+    }
+}
+
+/// statistik dihitung sekali saat deserialisasi, bukan pada setiap [`Buffer::stats`]
+impl<'de, T> serde::Deserialize<'de> for Buffer<T>
+where
+    T: Numeric + serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<T>::deserialize(deserializer).map(Self::from)
     }
 }
 
 impl<T: Sized> Default for Buffer<T> {
     #[inline(always)]
     fn default() -> Self {
-        Self(Vec::with_capacity(Self::MAX_CAP_BUFFER))
+        Self {
+            data: Vec::with_capacity(Self::MAX_CAP_BUFFER),
+            ring: None,
+            spill: SpillFile::default(),
+            evicted: 0,
+            stats: None,
+        }
     }
 }
 
@@ -63,13 +347,166 @@ impl<T: Sized> Buffer<T> {
     pub const MAX_CAP_BUFFER: usize = 30_000;
 }
 
+impl<T: Numeric> Buffer<T> {
+    /// ## ring buffer that keep the last `capacity` samples
+    /// ```
+    /// use dyno_core::Buffer;
+    /// let mut buffer = Buffer::<f64>::ring(8);
+    /// buffer.extend((0..100).map(|x| x as f64));
+    /// assert!(buffer.len() <= 9);
+    /// assert_eq!(buffer.last_value(), 99.0);
+    /// assert_eq!(buffer.evicted() + buffer.len(), 100);
+    /// ```
+    pub fn ring(capacity: usize) -> Self {
+        let mut buffer = Self::new_ring_buf(capacity);
+        buffer.set_ring(Some(RingConfig {
+            capacity,
+            overflow: RingOverflow::Evict,
+        }));
+        buffer
+    }
+
+    /// ## ring buffer that spill old samples into file at `path`
+    pub fn ring_spill(capacity: usize, path: impl Into<std::path::PathBuf>) -> Self {
+        let mut buffer = Self::new_ring_buf(capacity);
+        buffer.set_ring(Some(RingConfig {
+            capacity,
+            overflow: RingOverflow::Spill(path.into()),
+        }));
+        buffer
+    }
+
+    #[inline]
+    fn new_ring_buf(capacity: usize) -> Self {
+        let capacity = Ord::max(capacity, 1);
+        Self {
+            data: Vec::with_capacity(capacity + Ord::max(capacity / 8, 1)),
+            ring: None,
+            spill: SpillFile::default(),
+            evicted: 0,
+            stats: None,
+        }
+    }
+
+    /// ## enable or disable (`None`) the ring buffer mode
+    /// sample yang melebihi kapasitas langsung dikeluarkan
+    pub fn set_ring(&mut self, ring: Option<RingConfig>) {
+        self.ring = ring.map(|ring| RingConfig {
+            capacity: Ord::max(ring.capacity, 1),
+            ..ring
+        });
+        self.spill = SpillFile::new(match &self.ring {
+            Some(RingConfig {
+                overflow: RingOverflow::Spill(path),
+                ..
+            }) => Some(path.clone()),
+            _ => None,
+        });
+        if let Some(capacity) = self.ring.as_ref().map(|r| r.capacity) {
+            if self.data.len() > capacity {
+                self.evict(self.data.len() - capacity);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn ring_config(&self) -> Option<&RingConfig> {
+        self.ring.as_ref()
+    }
+
+    /// ## number of samples that has been evicted from the ring buffer
+    /// index sample pertama yang tersimpan, digunakan sebagai sumbu x pada `into_points`
+    #[inline]
+    pub const fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /// ## push a new sample, evict old samples in ring buffer mode
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.stats
+            .get_or_insert_with(|| BufferStats::from_values(&self.data))
+            .push(value);
+        self.data.push(value);
+        if let Some(ring) = &self.ring {
+            let limit = ring.capacity + ring.slack();
+            if self.data.len() >= limit {
+                self.evict(self.data.len() - ring.capacity);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|v| self.push(v))
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.evicted = 0;
+        self.stats = None;
+    }
+
+    /// ## statistics of the retained samples
+    /// O(1), kecuali setelah data diubah melalui `DerefMut` tanpa [`Buffer::refresh_stats`]
+    /// (dihitung ulang O(n) pada setiap pemanggilan sampai di-refresh atau `push` berikutnya)
+    #[inline]
+    pub fn stats(&self) -> BufferStats<T> {
+        match self.stats {
+            Some(stats) => stats,
+            None => BufferStats::from_values(&self.data),
+        }
+    }
+
+    /// ## recompute the running statistics after mutation through `DerefMut`
+    #[inline]
+    pub fn refresh_stats(&mut self) {
+        self.stats = Some(BufferStats::from_values(&self.data));
+    }
+
+    /// spill kolom [`crate::BufferData`] dalam ring mode, eviction diatur oleh `BufferData`
+    #[inline]
+    pub(crate) fn set_spill(&mut self, path: Option<std::path::PathBuf>) {
+        self.spill = SpillFile::new(path);
+    }
+
+    /// keluarkan `count` sample terlama, ditulis ke file spill jika ada
+    pub(crate) fn evict(&mut self, count: usize) {
+        let count = count.min(self.data.len());
+        self.spill.write(&self.data[..count]);
+        self.data.drain(..count);
+        self.evicted += count;
+        self.refresh_stats();
+    }
+
+    /// ## read samples spilled by [`Buffer::ring_spill`]
+    pub fn read_spill(path: impl AsRef<std::path::Path>) -> crate::DynoResult<Vec<T>> {
+        let bytes = std::fs::read(path)?;
+        Ok(bytes
+            .chunks_exact(8)
+            .map(|c| {
+                T::from_f64(f64::from_le_bytes([
+                    c[0], c[1], c[2], c[3], c[4], c[5], c[6], c[7],
+                ]))
+            })
+            .collect())
+    }
+}
+
 impl<T> Buffer<T>
 where
     T: Numeric + std::iter::Sum + SafeMath,
 {
     #[inline(always)]
     pub fn new_buf(cap: usize) -> Self {
-        Self(Vec::with_capacity(cap))
+        Self {
+            data: Vec::with_capacity(cap),
+            ring: None,
+            spill: SpillFile::default(),
+            evicted: 0,
+            stats: None,
+        }
     }
 
     #[inline(always)]
@@ -92,23 +529,23 @@ where
 
     #[inline(always)]
     pub fn min_value(&self) -> T {
-        self.iter()
-            .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .copied()
-            .unwrap_or_default()
+        self.stats().min
     }
 
     #[inline(always)]
     pub fn max_value(&self) -> T {
-        self.iter()
-            .max_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
-            .copied()
-            .unwrap_or_default()
+        self.stats().max
     }
 
     #[inline(always)]
     pub fn sum_value(&self) -> T {
-        self.iter().copied().sum::<T>()
+        self.stats().sum
+    }
+
+    /// ## population variance of the retained samples
+    #[inline(always)]
+    pub fn variance_value(&self) -> Float {
+        self.stats().variance()
     }
 
     #[inline(always)]
@@ -119,19 +556,7 @@ where
 
     #[inline(always)]
     pub fn into_points<Out: FromIterator<[f64; 2]>>(&self, showed: PointShowed) -> Out {
-        if showed == PointShowed::All {
-            return self
-                .iter()
-                .enumerate()
-                .map(|(idx, v)| [idx.to_f64(), v.to_f64()])
-                .collect::<Out>();
-        }
-        let len = showed.showed_len(self.len());
-        self[len..]
-            .iter()
-            .enumerate()
-            .map(|(i, v)| [(i + len).to_f64(), v.to_f64()])
-            .collect::<Out>()
+        self.into_points_map(showed, |v| v)
     }
 
     #[inline(always)]
@@ -140,19 +565,21 @@ where
         Out: FromIterator<[f64; 2]>,
         F: FnMut(f64) -> f64,
     {
-        if showed == PointShowed::All {
-            return self
-                .iter()
-                .enumerate()
-                .map(|(idx, v)| [idx.to_f64(), map_call(v.to_f64())])
-                .collect::<Out>();
-        }
-        let len = showed.showed_len(self.len());
-        self[len..]
+        let offset = self.evicted;
+        let start = showed.showed_len(self.len());
+        let points = self[start..]
             .iter()
             .enumerate()
-            .map(|(i, v)| [(i + len).to_f64(), map_call(v.to_f64())])
-            .collect::<Out>()
+            .map(|(i, v)| [(i + start + offset).to_f64(), map_call(v.to_f64())]);
+        match showed {
+            PointShowed::Downsample(n) => {
+                lttb(&points.collect::<Vec<_>>(), n).into_iter().collect()
+            }
+            PointShowed::MinMax(n) => min_max_decimate(&points.collect::<Vec<_>>(), n)
+                .into_iter()
+                .collect(),
+            _ => points.collect(),
+        }
     }
 }
 
@@ -162,6 +589,6 @@ where
 {
     #[inline]
     pub fn into_inner(&self) -> Vec<T> {
-        self.0.clone()
+        self.data.clone()
     }
 }
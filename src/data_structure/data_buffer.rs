@@ -1,7 +1,13 @@
 use crate::{convertions::prelude::*, Buffer, CsvSaver, Float, MotorType, Numeric, Stroke};
 use chrono::{NaiveDateTime, Utc};
+use std::ops::Range;
 
-use super::filter::DataFilter;
+use super::{
+    buffer::{RingConfig, RingOverflow},
+    filter::{DataFilter, Filter, FilterKind},
+    quality::{HampelConfig, SampleQuality},
+    resample::ResampleInfo,
+};
 
 #[derive(Debug, Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
 pub struct Data {
@@ -16,6 +22,24 @@ pub struct Data {
 
     pub percepatan_sudut: RadiansPerSecond,
     pub percepatan_roller: MetresPerSecond,
+
+    /// torsi dan horsepower setelah koreksi atmosfer, lihat [`crate::CorrectionStandard`]
+    pub torque_corrected: NewtonMeter,
+    pub horsepower_corrected: HorsePower,
+
+    /// kualitas sample, lihat [`SampleQuality`]
+    pub quality: SampleQuality,
+
+    /// channel tambahan dari [`crate::SerialDataV2`], disimpan pada kolom
+    /// [`BufferData::temp_2`] dan [`BufferData::load_cell`]
+    #[serde(skip)]
+    pub temp_2: Celcius,
+    #[serde(skip)]
+    pub load_cell: KiloGram,
+
+    /// jam sample dari akumulasi `period` firmware, `None` sebelum sample serial pertama
+    #[serde(skip)]
+    pub sample_clock: Option<NaiveDateTime>,
 }
 impl Data {
     pub fn new() -> Self {
@@ -29,42 +53,63 @@ impl Data {
         self.odo += tmp;
     }
     pub fn filter(&mut self, filter: &mut DataFilter) {
-        self.rpm_roda = filter.rpm_roda.next(self.rpm_roda);
-        self.rpm_engine = filter.rpm_engine.next(self.rpm_engine);
-        self.torque = filter.torque.next(self.torque);
-        self.horsepower = filter.horsepower.next(self.horsepower);
+        match filter.period(self.time_stamp.timestamp_millis()) {
+            Some(period) => {
+                self.rpm_roda = filter.rpm_roda.next_with_period(self.rpm_roda, period);
+                self.rpm_engine = filter.rpm_engine.next_with_period(self.rpm_engine, period);
+                self.torque = filter.torque.next_with_period(self.torque, period);
+                self.horsepower = filter.horsepower.next_with_period(self.horsepower, period);
+            }
+            None => {
+                self.rpm_roda = filter.rpm_roda.next(self.rpm_roda);
+                self.rpm_engine = filter.rpm_engine.next(self.rpm_engine);
+                self.torque = filter.torque.next(self.torque);
+                self.horsepower = filter.horsepower.next(self.horsepower);
+            }
+        }
+    }
+    /// ## apply atmospheric correction `factor` to torque and horsepower
+    #[inline]
+    pub fn correct(&mut self, factor: Float) {
+        self.torque_corrected = self.torque * factor;
+        self.horsepower_corrected = self.horsepower * factor;
     }
 
     pub fn from_serial(
         &mut self,
         config: &'_ mut crate::config::DynoConfig,
-        serial_data: super::SerialData,
+        serial_data: impl Into<super::serial::SerialPayload>,
     ) {
+        let serial_data = serial_data.into();
+        let base = serial_data.base();
         let super::SerialData {
             period,
-            pulse_enc_max,
-            pulse_enc,
             pulse_rpm,
             temperature,
             ..
-        } = serial_data;
+        } = base;
 
         let delta_ms = period as Float;
 
-        // let pulse_enc = if pulse_enc > 2_000_000 {
-        // } else {
-        // }
-        let putaran = pulse_enc as Float / pulse_enc_max as Float;
+        let (putaran, mut quality) = config.encoder.revolutions(&base);
+        if let super::serial::SerialPayload::V2(ext) = serial_data {
+            if !(ext.temperature_2.is_finite() && ext.load_cell.is_finite()) {
+                quality |= SampleQuality::SENSOR_FAULT;
+            }
+            self.temp_2 = quality.substitute(Celcius::new(ext.temperature_2), self.temp_2);
+            self.load_cell = quality.substitute(KiloGram::new(ext.load_cell), self.load_cell);
+        }
 
         let jarak_tempuh_roller = config.keliling_roller * putaran;
         self.odo += jarak_tempuh_roller.to_kilometres();
 
         let percepatan_roller = MetresPerSecond::from_ms(jarak_tempuh_roller, delta_ms);
-        let speed = percepatan_roller
-            .to_kilometres_per_hour()
-            .if_not_normal(self.speed);
+        let speed = quality.substitute(percepatan_roller.to_kilometres_per_hour(), self.speed);
 
-        let rpm_roda = RotationPerMinute::from_rot(putaran, delta_ms).if_not_normal(self.rpm_roda);
+        let rpm_roda = quality.substitute(
+            RotationPerMinute::from_rot(putaran, delta_ms),
+            self.rpm_roda,
+        );
         let rpm_engine = match &config.motor_type {
             MotorType::Engine => match config.motor_info.stroke {
                 Stroke::Four => RotationPerMinute::from_rot(
@@ -74,21 +119,28 @@ impl Data {
                 _ => RotationPerMinute::from_rot(pulse_rpm as Float, delta_ms),
             },
             MotorType::Electric => RotationPerMinute::from_rot(pulse_rpm as Float, delta_ms),
-        }
-        .if_not_normal(self.rpm_engine);
+        };
+        let rpm_engine = quality.substitute(rpm_engine, self.rpm_engine);
 
-        let percepatan_sudut = rpm_roda.to_radians_per_second();
-        self.torque = NewtonMeter::new(
-            (config.inertia_roller_beban() * (percepatan_sudut - self.percepatan_sudut).to_float())
-                * config.perbandingan_gear(),
-        )
-        .if_negative_normal(self.torque);
+        // jitter waktu terima serial tidak boleh masuk ke dt estimator turunan
+        self.time_stamp = match self.sample_clock {
+            Some(clock) => clock + chrono::Duration::milliseconds(period as i64),
+            None => Utc::now().naive_local(),
+        };
+        self.sample_clock = Some(self.time_stamp);
+        config.rebase_resumed(self.time_stamp.timestamp_millis(), period as i64);
 
-        self.horsepower =
-            HorsePower::from_nm(self.torque, rpm_roda).if_negative_normal(self.horsepower);
-        self.temp = Celcius::new(temperature).if_not_normal(self.temp);
-        self.time_stamp = Utc::now().naive_local();
+        let percepatan_sudut = rpm_roda.to_radians_per_second();
+        let alpha = config
+            .derivative
+            .next(self.time_stamp.timestamp_millis(), percepatan_sudut.value());
+        self.torque = quality.clip(config.roller_torque(alpha), self.torque);
+        self.horsepower = quality.clip(HorsePower::from_nm(self.torque, rpm_roda), self.horsepower);
+        self.temp = config
+            .quality
+            .check_temp(Celcius::new(temperature), self.temp, &mut quality);
 
+        self.quality = quality;
         self.speed = speed;
         self.rpm_roda = rpm_roda;
         self.percepatan_sudut = percepatan_sudut;
@@ -126,6 +178,16 @@ impl Data {
         self.horsepower = next_row()?.into();
         self.temp = next_row()?.into();
         self.time_stamp = row_iter.next()?.as_datetime()?;
+        // kolom channel V2, tidak ada pada file excel lama
+        let mut next_extra = || {
+            row_iter
+                .next()
+                .and_then(|x| x.get_float().or(x.get_int().map(|v| v.to_f64())))
+                .unwrap_or_default()
+        };
+        self.temp_2 = next_extra().into();
+        self.load_cell = next_extra().into();
+        self.correct(1.0);
         Some(())
     }
 
@@ -141,12 +203,32 @@ impl Data {
         self.temp = pnext()?.into();
         self.time_stamp =
             NaiveDateTime::from_timestamp_millis(itw.next().and_then(|x| x.parse().ok())?)?;
+        // kolom channel V2, tidak ada pada file csv lama
+        let mut pextra = || {
+            itw.next()
+                .and_then(|x| x.parse::<Float>().ok())
+                .unwrap_or_default()
+        };
+        self.temp_2 = pextra().into();
+        self.load_cell = pextra().into();
+        self.correct(1.0);
 
         Some(())
     }
 }
 
+/// layout biner [`BufferData`] pada file `.dyno`, naikkan setiap ada perubahan field
+/// dan tambahkan fallback untuk layout sebelumnya pada deserialisasi.
+/// versi 2 menambahkan [`RawCapture::warmup`] setelah body,
+/// versi 3 menambahkan `rpm_roda_smooth` dan `rpm_engine_smooth` setelah warm-up,
+/// versi 4 menambahkan `temp_2` dan `load_cell` setelah rpm smooth
+pub const DYNO_FORMAT_VERSION: u32 = 4;
+
+/// header file `.dyno`, file tanpa header adalah layout sebelum [`DYNO_FORMAT_VERSION`] 1
+const DYNO_FORMAT_MAGIC: u64 = u64::from_le_bytes(*b"DYNOFILE");
+
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(remote = "Self")]
 pub struct BufferData {
     pub speed: Buffer<KilometresPerHour>,
     pub rpm_roda: Buffer<RotationPerMinute>,
@@ -155,13 +237,270 @@ pub struct BufferData {
     pub horsepower: Buffer<HorsePower>,
     pub temp: Buffer<Celcius>,
     pub time_stamp: Buffer<i64>,
+    pub torque_corrected: Buffer<NewtonMeter>,
+    pub horsepower_corrected: Buffer<HorsePower>,
+    /// torque dan horsepower tanpa lag hasil [`BufferData::smooth`], kosong jika belum dihitung
+    pub torque_smooth: Buffer<NewtonMeter>,
+    pub horsepower_smooth: Buffer<HorsePower>,
+    /// rpm tanpa lag hasil [`BufferData::smooth`] untuk sumbu x kurva daya,
+    /// disimpan setelah body pada file `.dyno`
+    #[serde(skip)]
+    pub rpm_roda_smooth: Buffer<RotationPerMinute>,
+    #[serde(skip)]
+    pub rpm_engine_smooth: Buffer<RotationPerMinute>,
+    /// channel [`crate::SerialDataV2`], bernilai 0 untuk payload V1,
+    /// disimpan setelah rpm smooth pada file `.dyno`
+    #[serde(skip)]
+    pub temp_2: Buffer<Celcius>,
+    #[serde(skip)]
+    pub load_cell: Buffer<KiloGram>,
+    /// kualitas setiap sample
+    pub quality: Vec<SampleQuality>,
 
     pub data: Data,
     pub len: usize,
 
+    /// payload mentah dari serial, untuk menghitung ulang data dengan config lain
+    pub raw: RawCapture,
+    /// sample rate asli jika buffer ini hasil [`BufferData::resample`]
+    pub resample_info: Option<ResampleInfo>,
+
     #[serde(skip)]
     #[serde(default)]
     pub total_time: u64,
+
+    /// ring buffer mode seluruh kolom, lihat [`BufferData::set_ring`]
+    #[serde(skip)]
+    pub(super) ring: Option<RingConfig>,
+}
+
+impl serde::Serialize for BufferData {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        struct Body<'a>(&'a BufferData);
+        impl serde::Serialize for Body<'_> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                BufferData::serialize(self.0, serializer)
+            }
+        }
+
+        if serializer.is_human_readable() {
+            return BufferData::serialize(self, serializer);
+        }
+        let mut tuple = serializer.serialize_tuple(8)?;
+        tuple.serialize_element(&DYNO_FORMAT_MAGIC)?;
+        tuple.serialize_element(&DYNO_FORMAT_VERSION)?;
+        tuple.serialize_element(&Body(self))?;
+        tuple.serialize_element(&self.raw.warmup)?;
+        tuple.serialize_element(&self.rpm_roda_smooth)?;
+        tuple.serialize_element(&self.rpm_engine_smooth)?;
+        tuple.serialize_element(&self.temp_2)?;
+        tuple.serialize_element(&self.load_cell)?;
+        tuple.end()
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BufferData {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            return BufferData::deserialize(deserializer);
+        }
+        deserializer.deserialize_tuple(usize::MAX, dyno_format::VersionVisitor)
+    }
+}
+
+/// header versi file `.dyno` dan layout [`BufferData`] sebelum ada header versi
+mod dyno_format {
+    use super::{BufferData, Data, DYNO_FORMAT_MAGIC, DYNO_FORMAT_VERSION};
+    use crate::{convertions::prelude::*, Buffer};
+    use chrono::NaiveDateTime;
+    use serde::de::{Error, SeqAccess, Visitor};
+
+    #[derive(serde::Deserialize)]
+    struct LegacyData {
+        speed: KilometresPerHour,
+        torque: NewtonMeter,
+        horsepower: HorsePower,
+        temp: Celcius,
+        time_stamp: NaiveDateTime,
+        rpm_roda: RotationPerMinute,
+        rpm_engine: RotationPerMinute,
+        odo: KiloMetres,
+        percepatan_sudut: RadiansPerSecond,
+        percepatan_roller: MetresPerSecond,
+    }
+
+    impl From<LegacyData> for Data {
+        fn from(value: LegacyData) -> Self {
+            let mut data = Self {
+                speed: value.speed,
+                torque: value.torque,
+                horsepower: value.horsepower,
+                temp: value.temp,
+                time_stamp: value.time_stamp,
+                rpm_roda: value.rpm_roda,
+                rpm_engine: value.rpm_engine,
+                odo: value.odo,
+                percepatan_sudut: value.percepatan_sudut,
+                percepatan_roller: value.percepatan_roller,
+                ..Default::default()
+            };
+            data.correct(1.0);
+            data
+        }
+    }
+
+    /// field setelah `speed`, panjang `speed` sudah dibaca sebagai pengganti header
+    #[derive(serde::Deserialize)]
+    struct LegacyTail {
+        rpm_roda: Buffer<RotationPerMinute>,
+        rpm_engine: Buffer<RotationPerMinute>,
+        torque: Buffer<NewtonMeter>,
+        horsepower: Buffer<HorsePower>,
+        temp: Buffer<Celcius>,
+        time_stamp: Buffer<i64>,
+        data: LegacyData,
+        len: usize,
+    }
+
+    /// isi [`BufferData`] setelah header, di-deserialisasi dengan layout saat ini
+    struct Body(BufferData);
+    impl<'de> serde::Deserialize<'de> for Body {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            BufferData::deserialize(deserializer).map(Self)
+        }
+    }
+
+    pub(super) struct VersionVisitor;
+
+    impl<'de> Visitor<'de> for VersionVisitor {
+        type Value = BufferData;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("dyno file with version header or legacy layout")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let missing = || A::Error::custom("unexpected end of dyno file");
+            let header = seq.next_element::<u64>()?.ok_or_else(missing)?;
+            if header == DYNO_FORMAT_MAGIC {
+                let version = seq.next_element::<u32>()?.ok_or_else(missing)?;
+                if !(1..=DYNO_FORMAT_VERSION).contains(&version) {
+                    return Err(A::Error::custom(format!(
+                        "unsupported dyno file version {version} (latest {DYNO_FORMAT_VERSION})"
+                    )));
+                }
+                let mut buffer = seq.next_element::<Body>()?.ok_or_else(missing)?.0;
+                if version >= 2 {
+                    buffer.raw.warmup = seq.next_element()?.ok_or_else(missing)?;
+                }
+                if version >= 3 {
+                    buffer.rpm_roda_smooth = seq.next_element()?.ok_or_else(missing)?;
+                    buffer.rpm_engine_smooth = seq.next_element()?.ok_or_else(missing)?;
+                }
+                match version >= 4 {
+                    true => {
+                        buffer.temp_2 = seq.next_element()?.ok_or_else(missing)?;
+                        buffer.load_cell = seq.next_element()?.ok_or_else(missing)?;
+                        buffer.data.temp_2 = buffer.temp_2.last().copied().unwrap_or_default();
+                        buffer.data.load_cell =
+                            buffer.load_cell.last().copied().unwrap_or_default();
+                    }
+                    false => {
+                        buffer
+                            .temp_2
+                            .extend((0..buffer.len).map(|_| Celcius::default()));
+                        buffer
+                            .load_cell
+                            .extend((0..buffer.len).map(|_| KiloGram::default()));
+                    }
+                }
+                return Ok(buffer);
+            }
+
+            // layout lama: `header` adalah panjang buffer `speed`
+            let speed = (0..header)
+                .map(|_| seq.next_element::<KilometresPerHour>()?.ok_or_else(missing))
+                .collect::<Result<Vec<_>, _>>()?;
+            let tail = seq.next_element::<LegacyTail>()?.ok_or_else(missing)?;
+            let len = speed.len();
+            let columns_len = [
+                tail.rpm_roda.len(),
+                tail.rpm_engine.len(),
+                tail.torque.len(),
+                tail.horsepower.len(),
+                tail.temp.len(),
+                tail.time_stamp.len(),
+            ];
+            if tail.len != len || columns_len.iter().any(|l| *l != len) {
+                return Err(A::Error::custom(
+                    "mismatch columns length in legacy dyno file",
+                ));
+            }
+
+            let mut buffer = BufferData::new();
+            for (idx, speed) in speed.into_iter().enumerate() {
+                buffer.data = Data {
+                    speed,
+                    rpm_roda: tail.rpm_roda[idx],
+                    rpm_engine: tail.rpm_engine[idx],
+                    torque: tail.torque[idx],
+                    horsepower: tail.horsepower[idx],
+                    temp: tail.temp[idx],
+                    time_stamp: NaiveDateTime::from_timestamp_millis(tail.time_stamp[idx])
+                        .unwrap_or_default(),
+                    ..Default::default()
+                };
+                buffer.data.correct(1.0);
+                buffer.process_data();
+            }
+            buffer.data = tail.data.into();
+            Ok(buffer)
+        }
+    }
+}
+
+/// # Raw Capture
+/// payload [`crate::SerialPayload`] yang diterima [`BufferData::push_from_serial`],
+/// hanya direkam jika `enabled`
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RawCapture {
+    pub enabled: bool,
+    /// timestamp (ms) sample pertama
+    pub start: i64,
+    pub frames: Vec<crate::SerialPayload>,
+    /// payload sebelum sample pertama direkam (warm-up [`super::session::DynoSession`]),
+    /// hanya memperbarui state filter dan estimator turunan, lihat [`BufferData::warmup_from_serial`].
+    /// disimpan setelah body [`BufferData`] pada file `.dyno`
+    #[serde(skip)]
+    pub warmup: Vec<crate::SerialPayload>,
+}
+
+impl RawCapture {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.frames.clear();
+        self.warmup.clear();
+    }
+
+    fn push(&mut self, frame: crate::SerialPayload, time_stamp: NaiveDateTime) {
+        if self.frames.is_empty() {
+            self.start = time_stamp.timestamp_millis();
+        }
+        self.frames.push(frame);
+    }
 }
 
 #[repr(usize)]
@@ -174,6 +513,8 @@ pub enum DBIdx {
     Hp,
     Temp,
     TimeStamp,
+    Temp2,
+    LoadCell,
     SizeMax,
 }
 
@@ -187,6 +528,8 @@ impl BufferData {
         "HORSEPOWER (HP)",
         "TEMPERATURE (°C)",
         "TIMESTAMP",
+        "TEMPERATURE 2 (°C)",
+        "LOAD CELL (kg)",
     ];
 
     pub fn new() -> Self {
@@ -202,8 +545,19 @@ impl BufferData {
         self.horsepower.clear();
         self.temp.clear();
         self.time_stamp.clear();
+        self.torque_corrected.clear();
+        self.horsepower_corrected.clear();
+        self.torque_smooth.clear();
+        self.horsepower_smooth.clear();
+        self.rpm_roda_smooth.clear();
+        self.rpm_engine_smooth.clear();
+        self.temp_2.clear();
+        self.load_cell.clear();
+        self.quality.clear();
         self.data = Default::default();
         self.len = 0;
+        self.raw.clear();
+        self.resample_info = None;
     }
 
     #[inline(always)]
@@ -221,27 +575,409 @@ impl BufferData {
         self.temp.push(self.data.temp);
         self.time_stamp
             .push(self.data.time_stamp.timestamp_millis() as _);
+        self.torque_corrected.push(self.data.torque_corrected);
+        self.horsepower_corrected
+            .push(self.data.horsepower_corrected);
+        self.temp_2.push(self.data.temp_2);
+        self.load_cell.push(self.data.load_cell);
+        self.quality.push(self.data.quality);
         self.len += 1;
+        if let Some(ring) = &self.ring {
+            if self.len >= ring.capacity + ring.slack() {
+                self.evict(self.len - ring.capacity);
+            }
+        }
+    }
+
+    /// ## enable or disable (`None`) the ring buffer mode of every column
+    /// seluruh kolom, `quality` dan raw capture dikeluarkan bersamaan dengan aturan yang sama
+    /// seperti [`Buffer::ring`], sehingga index sample tetap sejajar antar kolom.
+    /// pada [`RingOverflow::Spill`] path adalah direktori, setiap kolom ditulis ke
+    /// `<path>/<kolom>.bin` (lihat [`Buffer::read_spill`]), `quality` yang keluar dibuang.
+    /// frame raw yang keluar menjadi [`RawCapture::warmup`] untuk [`BufferData::recompute`].
+    /// ```
+    /// use dyno_core::*;
+    /// let mut buffer = BufferData::new();
+    /// buffer.set_ring(Some(RingConfig { capacity: 100, overflow: RingOverflow::Evict }));
+    /// buffer.extend_data(vec![Data::default(); 1000]);
+    /// assert_eq!(buffer.evicted() + buffer.len(), 1000);
+    /// assert_eq!(buffer.quality.len(), buffer.speed.len());
+    /// ```
+    pub fn set_ring(&mut self, ring: Option<RingConfig>) {
+        let ring = ring.map(|ring| RingConfig {
+            capacity: Ord::max(ring.capacity, 1),
+            ..ring
+        });
+        let dir = match &ring {
+            Some(RingConfig {
+                overflow: RingOverflow::Spill(dir),
+                ..
+            }) => Some(dir.as_path()),
+            _ => None,
+        };
+        let path = |name: &str| dir.map(|dir| dir.join(format!("{name}.bin")));
+        self.speed.set_spill(path("speed"));
+        self.rpm_roda.set_spill(path("rpm_roda"));
+        self.rpm_engine.set_spill(path("rpm_engine"));
+        self.torque.set_spill(path("torque"));
+        self.horsepower.set_spill(path("horsepower"));
+        self.temp.set_spill(path("temp"));
+        self.time_stamp.set_spill(path("time_stamp"));
+        self.torque_corrected.set_spill(path("torque_corrected"));
+        self.horsepower_corrected
+            .set_spill(path("horsepower_corrected"));
+        self.torque_smooth.set_spill(path("torque_smooth"));
+        self.horsepower_smooth.set_spill(path("horsepower_smooth"));
+        self.rpm_roda_smooth.set_spill(path("rpm_roda_smooth"));
+        self.rpm_engine_smooth.set_spill(path("rpm_engine_smooth"));
+        self.temp_2.set_spill(path("temp_2"));
+        self.load_cell.set_spill(path("load_cell"));
+        self.ring = ring;
+        if let Some(capacity) = self.ring.as_ref().map(|ring| ring.capacity) {
+            if self.len > capacity {
+                self.evict(self.len - capacity);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn ring_config(&self) -> Option<&RingConfig> {
+        self.ring.as_ref()
+    }
+
+    /// ## number of samples that has been evicted in ring buffer mode
+    #[inline]
+    pub fn evicted(&self) -> usize {
+        self.time_stamp.evicted()
+    }
+
+    /// keluarkan `count` sample terlama dari seluruh kolom
+    fn evict(&mut self, count: usize) {
+        let count = count.min(self.len);
+        // frame raw sejajar dengan sample terakhir jika raw capture diaktifkan di tengah rekaman
+        let offset = self.len - self.raw.len().min(self.len);
+        let frames = count.saturating_sub(offset).min(self.raw.len());
+        if frames > 0 {
+            self.raw.start = self.time_stamp.get(count).copied().unwrap_or_default();
+            let warmup = &mut self.raw.warmup;
+            warmup.extend(self.raw.frames.drain(..frames));
+            if warmup.len() > FilterKind::MAX_WARMUP_LEN {
+                warmup.drain(..warmup.len() - FilterKind::MAX_WARMUP_LEN);
+            }
+        }
+        self.quality.drain(..count.min(self.quality.len()));
+        self.speed.evict(count);
+        self.rpm_roda.evict(count);
+        self.rpm_engine.evict(count);
+        self.torque.evict(count);
+        self.horsepower.evict(count);
+        self.temp.evict(count);
+        self.time_stamp.evict(count);
+        self.torque_corrected.evict(count);
+        self.horsepower_corrected.evict(count);
+        self.torque_smooth.evict(count);
+        self.horsepower_smooth.evict(count);
+        self.rpm_roda_smooth.evict(count);
+        self.rpm_engine_smooth.evict(count);
+        self.temp_2.evict(count);
+        self.load_cell.evict(count);
+        self.len -= count;
     }
 
     #[inline(always)]
     pub fn push_from_serial(
         &mut self,
         config: &'_ mut crate::config::DynoConfig,
-        serial_data: crate::SerialData,
+        serial_data: impl Into<crate::SerialPayload>,
+    ) {
+        let serial_data = serial_data.into();
+        self.update_from_serial(config, serial_data);
+        self.record_serial(config, serial_data);
+    }
+
+    /// ## push the last [`Data`] that already updated from `serial_data` into buffers
+    /// beserta raw capture dan penandaan outlier, lihat [`BufferData::update_from_serial`]
+    pub(crate) fn record_serial(
+        &mut self,
+        config: &'_ crate::config::DynoConfig,
+        serial_data: crate::SerialPayload,
+    ) {
+        self.total_time += serial_data.base().period as u64;
+        // raw lebih dulu agar ikut dikeluarkan bersama kolom pada ring mode
+        if self.raw.enabled {
+            self.raw.push(serial_data, self.data.time_stamp);
+        }
+        self.process_data();
+        let hampel = config.quality.outlier;
+        if hampel.enabled && self.len > hampel.half_window * 2 {
+            self.mark_outlier(&hampel, self.len - 1 - hampel.half_window);
+        }
+    }
+
+    /// ## recompute every derived column from the raw capture with a new `config`
+    /// filter dan estimator pada `config` direset sebelum diproses ulang,
+    /// return error if this buffer has no raw capture
+    /// ```
+    /// use dyno_core::{simulator::DynoSimulator, *};
+    /// let mut config = DynoConfig::default();
+    /// let mut buffer = BufferData::new();
+    /// buffer.raw.enabled = true;
+    /// for serial in DynoSimulator::from_config(&config) {
+    ///     buffer.push_from_serial(&mut config, serial);
+    /// }
+    /// let speed = buffer.speed.max_value();
+    /// config.diameter_roller = config.diameter_roller * 2.0;
+    /// buffer.recompute(&mut config).unwrap();
+    /// assert!((buffer.speed.max_value().value() - speed.value() * 2.0).abs() < 1.0);
+    /// ```
+    pub fn recompute(
+        &mut self,
+        config: &'_ mut crate::config::DynoConfig,
+    ) -> crate::DynoResult<()> {
+        if self.raw.is_empty() {
+            return Err(crate::DynoErr::validation_error(
+                "Buffer has no raw capture to recompute from",
+            ));
+        }
+        config.init();
+        let raw = std::mem::take(&mut self.raw);
+        self.clean();
+        self.total_time = 0;
+        self.raw.enabled = true;
+        // timestamp sample pertama sama dengan rekaman awal
+        let first_period = raw.frames[0].base().period as i64;
+        let warmup_ms = raw
+            .warmup
+            .iter()
+            .map(|frame| frame.base().period as i64)
+            .sum::<i64>();
+        self.data.sample_clock =
+            NaiveDateTime::from_timestamp_millis(raw.start - first_period - warmup_ms);
+        // warm-up diproses ulang agar state filter sama dengan saat rekaman dimulai
+        for frame in raw.warmup.iter().copied() {
+            self.warmup_from_serial(config, frame);
+        }
+        for frame in raw.frames.iter().copied() {
+            self.push_from_serial(config, frame);
+        }
+        Ok(())
+    }
+
+    /// ## update the last [`Data`] from serial without pushing it into buffers
+    #[inline(always)]
+    pub fn update_from_serial(
+        &mut self,
+        config: &'_ mut crate::config::DynoConfig,
+        serial_data: impl Into<crate::SerialPayload>,
     ) {
-        self.total_time += serial_data.period as u64;
         self.data.from_serial(config, serial_data);
         self.data.filter(&mut config.filter);
-        self.process_data();
+        self.data.correct(config.correction_factor());
+    }
+
+    /// ## update the last [`Data`] from serial before the first recorded sample
+    /// payload ikut disimpan pada [`RawCapture::warmup`] jika raw capture aktif,
+    /// hanya frame terakhir sepanjang [`crate::DynoConfig::warmup_len`] yang disimpan
+    pub fn warmup_from_serial(
+        &mut self,
+        config: &'_ mut crate::config::DynoConfig,
+        serial_data: impl Into<crate::SerialPayload>,
+    ) {
+        let serial_data = serial_data.into();
+        self.update_from_serial(config, serial_data);
+        if self.raw.enabled {
+            let limit = config.warmup_len(serial_data.base().period);
+            let warmup = &mut self.raw.warmup;
+            warmup.push(serial_data);
+            if warmup.len() > limit {
+                warmup.drain(..warmup.len() - limit);
+            }
+        }
     }
 
     pub fn push_from_data(&mut self, config: &'_ mut crate::config::DynoConfig, data: Data) {
         self.data.from_self(data);
         self.data.filter(&mut config.filter);
+        self.data.correct(config.correction_factor());
         self.process_data();
     }
 
+    /// ## flag `index` as [`SampleQuality::OUTLIER`] if any channel is a spike
+    /// channel yang diperiksa: speed, rpm roda, rpm engine, torque dan horsepower
+    pub fn mark_outlier(&mut self, hampel: &HampelConfig, index: usize) -> bool {
+        fn check<T: Numeric>(hampel: &HampelConfig, column: &[T], index: usize) -> bool {
+            let start = index.saturating_sub(hampel.half_window);
+            let end = (index + hampel.half_window + 1).min(column.len());
+            if index >= end {
+                return false;
+            }
+            let values = column[start..end]
+                .iter()
+                .map(|v| v.to_float())
+                .collect::<Vec<_>>();
+            hampel.is_outlier(&values, index - start)
+        }
+        let outlier = check(hampel, &self.speed, index)
+            || check(hampel, &self.rpm_roda, index)
+            || check(hampel, &self.rpm_engine, index)
+            || check(hampel, &self.torque, index)
+            || check(hampel, &self.horsepower, index);
+        if let (true, Some(quality)) = (outlier, self.quality.get_mut(index)) {
+            quality.insert(SampleQuality::OUTLIER);
+        }
+        outlier
+    }
+
+    /// ## flag every outlier in this buffer, return the number of flagged samples
+    /// nilai sample tidak diubah, hanya ditandai [`SampleQuality::OUTLIER`]
+    pub fn detect_outliers(&mut self, hampel: &HampelConfig) -> usize {
+        if self.quality.len() < self.len {
+            self.quality.resize(self.len, SampleQuality::OK);
+        }
+        (0..self.len)
+            .filter(|idx| self.mark_outlier(hampel, *idx))
+            .count()
+    }
+
+    /// ## angular acceleration of the roller (rad/s²) of every sample
+    pub fn angular_acceleration(&self, kind: super::derivative::DerivativeKind) -> Vec<Float> {
+        let omega = self
+            .rpm_roda
+            .iter()
+            .map(|rpm| rpm.to_radians_per_second().value())
+            .collect::<Vec<_>>();
+        kind.derive(&self.time_stamp, &omega)
+    }
+
+    /// ## recompute corrected torque and horsepower columns with new correction `factor`
+    /// e.g. after the ambient conditions of recorded data is changed
+    /// ```
+    /// use dyno_core::*;
+    /// let mut buffer = BufferData::new();
+    /// buffer.extend_data([Data { torque: NewtonMeter(10.0), ..Default::default() }]);
+    /// buffer.apply_correction(1.05);
+    /// assert_eq!(buffer.torque_corrected[0], NewtonMeter(10.5));
+    /// ```
+    pub fn apply_correction(&mut self, factor: Float) {
+        self.torque_corrected.clear();
+        self.torque_corrected
+            .extend(self.torque.iter().map(|t| *t * factor));
+        self.horsepower_corrected.clear();
+        self.horsepower_corrected
+            .extend(self.horsepower.iter().map(|h| *h * factor));
+        self.data.correct(factor);
+    }
+
+    /// ## zero-phase smoothing of torque, horsepower and rpm with filter `kind`
+    /// kolom dihitung ulang dari raw capture tanpa filter realtime `config.filter`, karena lag
+    /// filter causal yang sudah ada pada kolom tidak bisa dihilangkan oleh filtfilt.
+    /// tanpa raw capture (mis. data dari csv / excel) filtfilt dijalankan pada kolom tersimpan,
+    /// sehingga lag filter realtime saat rekaman masih tersisa pada hasilnya.
+    /// hasilnya disimpan pada kolom `*_smooth`, kolom lain tidak diubah.
+    /// return error if the raw capture does not match the recorded samples,
+    /// lihat [`FilterKind::filtfilt`]
+    pub fn smooth(
+        &mut self,
+        config: &'_ crate::config::DynoConfig,
+        kind: FilterKind,
+    ) -> crate::DynoResult<()> {
+        fn fill<T: Numeric>(column: &mut Buffer<T>, values: Vec<T>) {
+            column.clear();
+            column.extend(values);
+        }
+        let unfiltered;
+        let source = match self.raw.is_empty() {
+            true => &*self,
+            false => {
+                unfiltered = self.unfiltered(config)?;
+                &unfiltered
+            }
+        };
+        let time_ms = &source.time_stamp;
+        let torque = kind.filtfilt(time_ms, &source.torque);
+        let horsepower = kind.filtfilt(time_ms, &source.horsepower);
+        let rpm_roda = kind.filtfilt(time_ms, &source.rpm_roda);
+        let rpm_engine = kind.filtfilt(time_ms, &source.rpm_engine);
+        fill(&mut self.torque_smooth, torque);
+        fill(&mut self.horsepower_smooth, horsepower);
+        fill(&mut self.rpm_roda_smooth, rpm_roda);
+        fill(&mut self.rpm_engine_smooth, rpm_engine);
+        Ok(())
+    }
+
+    /// ## whether every `*_smooth` column is computed for all samples
+    #[inline]
+    pub fn is_smoothed(&self) -> bool {
+        self.len > 0
+            && [
+                self.torque_smooth.len(),
+                self.horsepower_smooth.len(),
+                self.rpm_roda_smooth.len(),
+                self.rpm_engine_smooth.len(),
+            ]
+            .iter()
+            .all(|len| *len == self.len)
+    }
+
+    /// buffer baru dari raw capture yang dihitung ulang dengan `config` tanpa filter realtime,
+    /// return error if there is no raw capture or it does not match the recorded samples
+    fn unfiltered(&self, config: &'_ crate::config::DynoConfig) -> crate::DynoResult<Self> {
+        let mut config = config.clone();
+        config.filter = DataFilter::with_kind(FilterKind::None);
+        let mut unfiltered = Self {
+            raw: self.raw.clone(),
+            ..Default::default()
+        };
+        unfiltered.recompute(&mut config)?;
+        if unfiltered.len != self.len {
+            return Err(crate::DynoErr::validation_error(
+                "Raw capture does not match the recorded samples",
+            ));
+        }
+        Ok(unfiltered)
+    }
+
+    /// ## roller rpm of every sample without the lag of the realtime filter
+    /// sumber berurutan: raw capture yang dihitung ulang tanpa filter, `rpm_roda_smooth`,
+    /// lalu `rpm_roda` yang masih mengandung lag filter causal (mis. data dari csv / excel)
+    pub fn rpm_roda_unfiltered(
+        &self,
+        config: &'_ crate::config::DynoConfig,
+    ) -> Vec<RotationPerMinute> {
+        if !self.raw.is_empty() {
+            match self.unfiltered(config) {
+                Ok(unfiltered) => return unfiltered.rpm_roda.to_vec(),
+                Err(err) => log::warn!("Using recorded rpm roda: {err}"),
+            }
+        }
+        match self.rpm_roda_smooth.len() == self.len {
+            true => self.rpm_roda_smooth.to_vec(),
+            false => self.rpm_roda.to_vec(),
+        }
+    }
+
+    /// ## angular velocity (rad/s) and acceleration (rad/s²) of the roller in sample `range`
+    /// dari [`BufferData::rpm_roda_unfiltered`] dengan turunan non-causal `config.derivative`,
+    /// window turunan tidak melewati batas `range`
+    pub fn roller_motion(
+        &self,
+        config: &'_ crate::config::DynoConfig,
+        range: Range<usize>,
+    ) -> (Vec<Float>, Vec<Float>) {
+        let rpm = self.rpm_roda_unfiltered(config);
+        let end = range.end.min(rpm.len()).min(self.time_stamp.len());
+        let range = range.start.min(end)..end;
+        let omega = rpm[range.clone()]
+            .iter()
+            .map(|rpm| rpm.to_radians_per_second().value())
+            .collect::<Vec<_>>();
+        let alpha = config
+            .derivative
+            .kind
+            .derive(&self.time_stamp[range], &omega);
+        (omega, alpha)
+    }
+
     pub fn extend_data(&mut self, data: impl AsRef<[Data]>) {
         data.as_ref().iter().copied().for_each(|d| {
             self.data.from_self(d);
@@ -249,6 +985,143 @@ impl BufferData {
         })
     }
 
+    /// [`Data`] dari kolom buffer pada `index`, field yang tidak disimpan bernilai default
+    pub(super) fn sample(&self, index: usize) -> Data {
+        fn at<T: Copy + Default>(column: &[T], index: usize) -> T {
+            column.get(index).copied().unwrap_or_default()
+        }
+        Data {
+            speed: at(&self.speed, index),
+            torque: at(&self.torque, index),
+            horsepower: at(&self.horsepower, index),
+            temp: at(&self.temp, index),
+            time_stamp: NaiveDateTime::from_timestamp_millis(at(&self.time_stamp, index))
+                .unwrap_or_default(),
+            rpm_roda: at(&self.rpm_roda, index),
+            rpm_engine: at(&self.rpm_engine, index),
+            torque_corrected: at(&self.torque_corrected, index),
+            horsepower_corrected: at(&self.horsepower_corrected, index),
+            quality: at(&self.quality, index),
+            temp_2: at(&self.temp_2, index),
+            load_cell: at(&self.load_cell, index),
+            ..Default::default()
+        }
+    }
+
+    /// ## index of the sample nearest to timestamp `time_ms`
+    /// binary search pada `time_stamp` yang monoton naik, return `None` jika buffer kosong
+    pub fn index_at_time(&self, time_ms: i64) -> Option<usize> {
+        let after = self.time_stamp.partition_point(|t| *t < time_ms);
+        match (after.checked_sub(1), self.time_stamp.get(after)) {
+            (Some(before), Some(t)) => {
+                let closer = time_ms - self.time_stamp[before] <= t - time_ms;
+                Some(crate::ternary!((closer)?(before): (after)))
+            }
+            (Some(before), None) => Some(before),
+            (None, Some(_)) => Some(after),
+            (None, None) => None,
+        }
+    }
+
+    /// ## index range of samples with timestamp in `start..=end` (ms)
+    #[inline]
+    pub fn range_by_time(&self, start: i64, end: i64) -> Range<usize> {
+        let first = self.time_stamp.partition_point(|t| *t < start);
+        let last = self.time_stamp.partition_point(|t| *t <= end);
+        first..last.max(first)
+    }
+
+    /// ## new [`BufferData`] with the samples in index `range`
+    /// kolom yang belum dihitung (mis. `torque_smooth`) tetap kosong,
+    /// raw capture ikut dipotong jika jumlah frame sama dengan jumlah sample,
+    /// frame sebelum `range` menjadi [`RawCapture::warmup`]
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        fn column<T: Numeric>(buffer: &Buffer<T>, range: &Range<usize>) -> Buffer<T> {
+            let end = range.end.min(buffer.len());
+            let values = &buffer[range.start.min(end)..end];
+            let mut column = Buffer::default();
+            column.extend(values.iter().copied());
+            column
+        }
+        let end = range.end.min(self.len);
+        let range = range.start.min(end)..end;
+        let data = match (end == self.len, end.checked_sub(1)) {
+            (false, Some(last)) => self.sample(last),
+            (true, Some(_)) => self.data,
+            (_, None) => Data::default(),
+        };
+        let raw = match self.raw.len() == self.len && !range.is_empty() {
+            true => RawCapture {
+                enabled: self.raw.enabled,
+                start: self
+                    .time_stamp
+                    .get(range.start)
+                    .copied()
+                    .unwrap_or_default(),
+                frames: self.raw.frames[range.clone()].to_vec(),
+                // sample sebelum potongan menjadi warm-up potongan
+                warmup: [&self.raw.warmup[..], &self.raw.frames[..range.start]].concat(),
+            },
+            false => RawCapture {
+                enabled: self.raw.enabled,
+                ..Default::default()
+            },
+        };
+        let quality_end = range.end.min(self.quality.len());
+        Self {
+            speed: column(&self.speed, &range),
+            rpm_roda: column(&self.rpm_roda, &range),
+            rpm_engine: column(&self.rpm_engine, &range),
+            torque: column(&self.torque, &range),
+            horsepower: column(&self.horsepower, &range),
+            temp: column(&self.temp, &range),
+            time_stamp: column(&self.time_stamp, &range),
+            torque_corrected: column(&self.torque_corrected, &range),
+            horsepower_corrected: column(&self.horsepower_corrected, &range),
+            torque_smooth: column(&self.torque_smooth, &range),
+            horsepower_smooth: column(&self.horsepower_smooth, &range),
+            rpm_roda_smooth: column(&self.rpm_roda_smooth, &range),
+            rpm_engine_smooth: column(&self.rpm_engine_smooth, &range),
+            temp_2: column(&self.temp_2, &range),
+            load_cell: column(&self.load_cell, &range),
+            quality: self.quality[range.start.min(quality_end)..quality_end].to_vec(),
+            data,
+            len: range.len(),
+            raw,
+            resample_info: self.resample_info,
+            total_time: match (
+                self.time_stamp.get(range.start),
+                self.time_stamp.get(end.saturating_sub(1)),
+            ) {
+                (Some(first), Some(last)) if !range.is_empty() => (last - first).max(0) as u64,
+                _ => 0,
+            },
+            ring: None,
+        }
+    }
+
+    /// ## new [`BufferData`] with the samples recorded in `start..=end` (timestamp ms)
+    /// ```
+    /// use dyno_core::*;
+    /// let data = (0..10)
+    ///     .map(|idx| Data {
+    ///         speed: KilometresPerHour(idx as Float),
+    ///         time_stamp: chrono::NaiveDateTime::from_timestamp_millis(idx * 100).unwrap(),
+    ///         ..Default::default()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// let mut buffer = BufferData::new();
+    /// buffer.extend_data(data);
+    /// assert_eq!(buffer.index_at_time(320), Some(3));
+    /// let slice = buffer.slice_by_time(200, 500);
+    /// assert_eq!(slice.len(), 4);
+    /// assert_eq!(slice.speed.max_value(), KilometresPerHour(5.0));
+    /// ```
+    #[inline]
+    pub fn slice_by_time(&self, start: i64, end: i64) -> Self {
+        self.slice(self.range_by_time(start, end))
+    }
+
     #[inline]
     pub fn time_fmt(&self) -> String {
         let seconds = (self.total_time / 1000) % 60;
@@ -316,12 +1189,12 @@ impl CsvSaver for BufferData {
     fn save_csv_from_writer<W: std::io::Write>(&self, writer: &mut W) -> crate::DynoResult<()> {
         writeln!(
             writer,
-            "SPEED,RPM(RODA),RPM(ENGINE),TORQUE,HORSEPOWER,TEMP,TIME"
+            "SPEED,RPM(RODA),RPM(ENGINE),TORQUE,HORSEPOWER,TEMP,TIME,TEMP2,LOAD_CELL"
         )?;
         for idx in 0usize..self.len {
             writeln!(
                 writer,
-                "{speed},{rpm_roda},{rpm_engine},{torque},{horsepower},{temp},{time}",
+                "{speed},{rpm_roda},{rpm_engine},{torque},{horsepower},{temp},{time},{temp_2},{load_cell}",
                 speed = self.speed[idx],
                 rpm_roda = self.rpm_roda[idx],
                 rpm_engine = self.rpm_engine[idx],
@@ -329,6 +1202,8 @@ impl CsvSaver for BufferData {
                 horsepower = self.horsepower[idx],
                 temp = self.temp[idx],
                 time = self.time_stamp[idx],
+                temp_2 = self.temp_2.get(idx).copied().unwrap_or_default(),
+                load_cell = self.load_cell.get(idx).copied().unwrap_or_default(),
             )
             .ok();
         }
@@ -457,6 +1332,24 @@ impl crate::ExcelSaver for BufferData {
                             log::error!("{err}")
                         }
                     }),
+                7 => self.temp_2.iter().enumerate().for_each(|(index, value)| {
+                    if let Err(err) =
+                        worksheet.write_number((index + 1) as _, col as _, value.to_f64())
+                    {
+                        log::error!("{err}")
+                    }
+                }),
+                8 => self
+                    .load_cell
+                    .iter()
+                    .enumerate()
+                    .for_each(|(index, value)| {
+                        if let Err(err) =
+                            worksheet.write_number((index + 1) as _, col as _, value.to_f64())
+                        {
+                            log::error!("{err}")
+                        }
+                    }),
                 _ => unreachable!(),
             };
         }
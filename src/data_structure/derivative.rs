@@ -0,0 +1,252 @@
+//! # Derivative Estimator
+//! estimasi turunan terhadap waktu (mis. percepatan sudut rad/s² dari kecepatan sudut rad/s)
+//! menggunakan timestamp setiap sample, sehingga hasilnya tidak bergantung pada sample rate.
+use std::collections::VecDeque;
+
+use crate::Float;
+
+/// metode estimasi turunan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DerivativeKind {
+    /// selisih dua sample terakhir, `(v[n] - v[n-1]) / (t[n] - t[n-1])`
+    Backward,
+    /// selisih ujung-ujung `window` sample, `(v[n] - v[n-w+1]) / (t[n] - t[n-w+1])`,
+    /// pada mode realtime hasilnya adalah turunan di tengah window (delay `window / 2` sample)
+    Central { window: usize },
+    /// turunan polinomial least-squares orde `order` (1 - 3) dari `window` sample (Savitzky–Golay),
+    /// pada mode realtime dievaluasi di sample terakhir
+    SavitzkyGolay { window: usize, order: usize },
+}
+
+crate::macros::impl_serde_tagged!(DerivativeKind {
+    Backward,
+    Central { window: usize },
+    SavitzkyGolay { window: usize, order: usize },
+});
+
+impl Default for DerivativeKind {
+    fn default() -> Self {
+        Self::SavitzkyGolay {
+            window: 7,
+            order: 2,
+        }
+    }
+}
+
+impl std::fmt::Display for DerivativeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Backward => f.write_str("Backward Difference"),
+            Self::Central { window } => write!(f, "Central Difference ({window} samples)"),
+            Self::SavitzkyGolay { window, order } => {
+                write!(f, "Savitzky-Golay ({window} samples, order {order})")
+            }
+        }
+    }
+}
+
+impl DerivativeKind {
+    /// jumlah sample yang dibutuhkan dalam satu window
+    pub fn window(self) -> usize {
+        match self {
+            Self::Backward => 2,
+            Self::Central { window } => Ord::max(window, 2),
+            Self::SavitzkyGolay { window, order } => Ord::max(window, order.clamp(1, 3) + 1),
+        }
+    }
+
+    /// ## derivative at time `at` from samples `(time ms, value)`
+    /// return `0.0` if there is not enough samples
+    fn estimate(self, samples: &[(i64, Float)], at: i64) -> Float {
+        let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+            return 0.0;
+        };
+        if samples.len() < 2 {
+            return 0.0;
+        }
+        let derivative = match self {
+            Self::Backward | Self::Central { .. } => {
+                let dt = (last.0 - first.0) as Float * 0.001;
+                crate::ternary!((dt > 0.0)?((last.1 - first.1) / dt): (0.0))
+            }
+            Self::SavitzkyGolay { order, .. } => {
+                let rows = samples.iter().map(|(t, v)| ((t - at) as Float * 0.001, *v));
+                poly_coefficient(rows, order.clamp(1, 3).min(samples.len() - 1), 1).unwrap_or(0.0)
+            }
+        };
+        crate::ternary!((derivative.is_finite())?(derivative): (0.0))
+    }
+
+    /// ## derivative of every sample of the recorded `values`
+    /// menggunakan window simetris di sekitar setiap sample (non-causal)
+    pub fn derive(self, time_ms: &[i64], values: &[Float]) -> Vec<Float> {
+        let len = time_ms.len().min(values.len());
+        let half = self.window() / 2;
+        let samples = time_ms[..len]
+            .iter()
+            .copied()
+            .zip(values[..len].iter().copied())
+            .collect::<Vec<_>>();
+        (0..len)
+            .map(|idx| {
+                let (start, end) = match self {
+                    Self::Backward => (idx.saturating_sub(1), idx + 1),
+                    _ => (idx.saturating_sub(half), (idx + half + 1).min(len)),
+                };
+                self.estimate(&samples[start..end], time_ms[idx])
+            })
+            .collect()
+    }
+}
+
+/// ## coefficient of `t^coefficient` of the least-squares polynomial of `order` (1 - 3)
+/// return `None` if the fitting failed
+pub(super) fn poly_coefficient(
+    rows: impl Iterator<Item = (Float, Float)>,
+    order: usize,
+    coefficient: usize,
+) -> Option<Float> {
+    match order {
+        0 | 1 => poly_fit::<2>(rows, coefficient),
+        2 => poly_fit::<3>(rows, coefficient),
+        _ => poly_fit::<4>(rows, coefficient),
+    }
+}
+
+/// `N` = orde + 1
+fn poly_fit<const N: usize>(
+    rows: impl Iterator<Item = (Float, Float)>,
+    coefficient: usize,
+) -> Option<Float> {
+    let rows = rows.map(|(t, v)| {
+        let mut x = [1.0; N];
+        for i in 1..N {
+            x[i] = x[i - 1] * t;
+        }
+        (x, v)
+    });
+    let fit = crate::analysis::least_squares(rows)?;
+    fit.coefficients.get(coefficient).copied()
+}
+
+/// # Derivative Estimator
+/// estimator realtime dengan sliding window, disimpan di [`crate::DynoConfig`] seperti
+/// [`super::filter::DataFilter`]
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct DerivativeEstimator {
+    pub kind: DerivativeKind,
+    /// hanya disimpan pada [`super::filter::FilterSnapshot`]
+    #[serde(skip)]
+    samples: VecDeque<(i64, Float)>,
+}
+
+impl DerivativeEstimator {
+    pub fn new(kind: DerivativeKind) -> Self {
+        Self {
+            kind,
+            samples: VecDeque::with_capacity(kind.window()),
+        }
+    }
+
+    #[inline]
+    pub fn reset(&mut self) {
+        self.samples.clear();
+    }
+
+    /// ## samples `(time ms, value)` in the sliding window
+    #[inline]
+    pub fn samples(&self) -> impl Iterator<Item = &(i64, Float)> {
+        self.samples.iter()
+    }
+
+    /// ## replace the sliding window, e.g. to resume a session from [`super::filter::FilterSnapshot`]
+    pub fn restore(&mut self, samples: impl IntoIterator<Item = (i64, Float)>) {
+        self.samples.clear();
+        self.samples.extend(samples);
+    }
+
+    /// ## shift the timestamp of every sample in the window by `offset_ms`
+    pub fn rebase(&mut self, offset_ms: i64) {
+        self.samples
+            .iter_mut()
+            .for_each(|(time_ms, _)| *time_ms += offset_ms);
+    }
+
+    /// ## push a new sample and return the derivative (value per second)
+    /// sample dengan timestamp tidak naik akan mereset window
+    pub fn next(&mut self, time_ms: i64, value: Float) -> Float {
+        if self.samples.back().is_some_and(|(t, _)| *t >= time_ms) {
+            self.samples.clear();
+        }
+        let window = self.kind.window();
+        while self.samples.len() >= window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time_ms, value));
+        self.kind.estimate(self.samples.make_contiguous(), time_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quadratic(t_ms: i64) -> Float {
+        let t = t_ms as Float * 0.001;
+        3.0 * t * t + 2.0 * t + 1.0
+    }
+
+    #[test]
+    fn test_estimator_linear() {
+        for kind in [
+            DerivativeKind::Backward,
+            DerivativeKind::Central { window: 5 },
+            DerivativeKind::SavitzkyGolay {
+                window: 7,
+                order: 1,
+            },
+        ] {
+            let mut estimator = DerivativeEstimator::new(kind);
+            assert_eq!(estimator.next(0, 1.0), 0.0);
+            // period tidak seragam, turunan tetap 4.0 / s
+            let mut last = 0.0;
+            for t in [20, 45, 60, 100, 120, 170, 200] {
+                last = estimator.next(t, 1.0 + 4.0 * t as Float * 0.001);
+            }
+            assert!((last - 4.0).abs() < 1e-9, "{kind}: {last}");
+        }
+    }
+
+    #[test]
+    fn test_estimator_savitzky_golay_quadratic() {
+        let mut estimator = DerivativeEstimator::new(DerivativeKind::SavitzkyGolay {
+            window: 7,
+            order: 2,
+        });
+        let mut last = 0.0;
+        for t in (0..=500).step_by(50) {
+            last = estimator.next(t, quadratic(t));
+        }
+        // d/dt (3t² + 2t + 1) pada t = 0.5
+        assert!((last - 5.0).abs() < 1e-6, "{last}");
+
+        estimator.reset();
+        assert_eq!(estimator.next(600, quadratic(600)), 0.0);
+    }
+
+    #[test]
+    fn test_derive_buffer() {
+        let time = (0..20).map(|i| i * 50).collect::<Vec<i64>>();
+        let values = time.iter().map(|t| quadratic(*t)).collect::<Vec<_>>();
+        let derived = DerivativeKind::SavitzkyGolay {
+            window: 5,
+            order: 2,
+        }
+        .derive(&time, &values);
+        assert_eq!(derived.len(), time.len());
+        for (t, d) in time.iter().zip(derived) {
+            let expected = 6.0 * (*t as Float * 0.001) + 2.0;
+            assert!((d - expected).abs() < 1e-6, "{t}: {d} != {expected}");
+        }
+    }
+}
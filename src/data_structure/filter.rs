@@ -1,48 +1,554 @@
-use crate::{ternary, HorsePower, NewtonMeter, Numeric, RotationPerMinute};
+//! # Filter
+//! filter realtime untuk setiap channel data, setiap filter mengimplementasikan [`Filter`].
+//! jenis filter setiap channel pada [`DataFilter`] dapat diatur dari config melalui [`FilterKind`].
+//! [`DataFilter`] hanya menyimpan [`FilterKind`] (config), state filter dan estimator turunan
+//! disimpan pada [`FilterSnapshot`], sehingga sesi yang terhenti dapat dilanjutkan tanpa diskontinuitas.
+//! filter berbasis waktu ([`FilterKind::TimeConstant`], [`FilterKind::ButterworthHz`]) menghitung
+//! koefisien dari period setiap sample, sehingga hasilnya tidak bergantung pada sample rate board.
+use std::{collections::VecDeque, marker::PhantomData};
 
-#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+use super::derivative::DerivativeEstimator;
+use crate::{ternary, Float, HorsePower, NewtonMeter, Numeric, RotationPerMinute};
+
+/// # Filter
+/// filter sample per sample (causal)
+pub trait Filter<T: Numeric> {
+    /// ## push a new sample and return the filtered value
+    fn next(&mut self, input: T) -> T;
+    /// ## push a new sample taken `period` seconds after the previous sample
+    /// filter berbasis jumlah sample mengabaikan `period`
+    #[inline]
+    fn next_with_period(&mut self, input: T, period: Float) -> T {
+        let _ = period;
+        self.next(input)
+    }
+    /// ## clear the filter state, configuration stay the same
+    fn reset(&mut self);
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct DataFilter {
-    pub torque: ExponentialFilter<NewtonMeter>,
-    pub horsepower: ExponentialFilter<HorsePower>,
-    pub rpm_roda: ExponentialFilter<RotationPerMinute>,
-    pub rpm_engine: ExponentialFilter<RotationPerMinute>,
+    #[serde(with = "channel_kind")]
+    pub torque: ChannelFilter<NewtonMeter>,
+    #[serde(with = "channel_kind")]
+    pub horsepower: ChannelFilter<HorsePower>,
+    #[serde(with = "channel_kind")]
+    pub rpm_roda: ChannelFilter<RotationPerMinute>,
+    #[serde(with = "channel_kind")]
+    pub rpm_engine: ChannelFilter<RotationPerMinute>,
+    /// timestamp (ms) sample sebelumnya, untuk period filter berbasis waktu
+    #[serde(skip)]
+    time_ms: Option<i64>,
+    /// timestamp (ms) sample terakhir [`FilterSnapshot`] yang belum di-rebase ke time base baru
+    #[serde(skip)]
+    resume_ms: Option<i64>,
 }
 impl Default for DataFilter {
     fn default() -> Self {
         Self {
-            torque: ExponentialFilter::new(2),
-            horsepower: ExponentialFilter::new(2),
-            rpm_roda: ExponentialFilter::new(100),
-            rpm_engine: ExponentialFilter::new(100),
+            torque: FilterKind::Exponential { period: 2 }.build(),
+            horsepower: FilterKind::Exponential { period: 2 }.build(),
+            rpm_roda: FilterKind::Exponential { period: 100 }.build(),
+            rpm_engine: FilterKind::Exponential { period: 100 }.build(),
+            time_ms: None,
+            resume_ms: None,
         }
     }
 }
 
 impl DataFilter {
-    #[allow(dead_code)]
+    /// ## every channel filtered with the same `kind`
+    pub fn with_kind(kind: FilterKind) -> Self {
+        Self {
+            torque: kind.build(),
+            horsepower: kind.build(),
+            rpm_roda: kind.build(),
+            rpm_engine: kind.build(),
+            time_ms: None,
+            resume_ms: None,
+        }
+    }
+
     #[inline]
     pub fn reset(&mut self) {
         self.torque.reset();
         self.horsepower.reset();
         self.rpm_roda.reset();
         self.rpm_engine.reset();
+        self.time_ms = None;
+        self.resume_ms = None;
+    }
+
+    /// ## shift the timestamp of the previous sample by `offset_ms`
+    #[inline]
+    pub fn rebase(&mut self, offset_ms: i64) {
+        if let Some(time_ms) = self.time_ms.as_mut() {
+            *time_ms += offset_ms;
+        }
+    }
+
+    /// ## last timestamp (ms) of the restored [`FilterSnapshot`], only once after restore
+    #[inline]
+    pub(crate) fn take_resume(&mut self) -> Option<i64> {
+        self.resume_ms.take()
+    }
+
+    /// ## number of samples until every channel filter settles, see [`FilterKind::warmup_len`]
+    pub fn warmup_len(&self, period: Float) -> usize {
+        [
+            self.torque.kind(),
+            self.horsepower.kind(),
+            self.rpm_roda.kind(),
+            self.rpm_engine.kind(),
+        ]
+        .into_iter()
+        .map(|kind| kind.warmup_len(period))
+        .max()
+        .unwrap_or_default()
+    }
+
+    /// ## period (seconds) from the previous sample to the sample at `time_ms`
+    /// return `None` for the first sample or timestamp that does not increase
+    pub fn period(&mut self, time_ms: i64) -> Option<Float> {
+        let previous = self.time_ms.replace(time_ms)?;
+        let period = (time_ms - previous) as Float * 0.001;
+        crate::ternary!((period > 0.0)?(Some(period)): (None))
+    }
+}
+
+/// orde Butterworth low-pass filter
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ButterworthOrder {
+    /// satu section biquad, -40 dB/dekade
+    #[default]
+    Second,
+    /// dua section biquad, -80 dB/dekade
+    Fourth,
+}
+
+/// jenis filter yang digunakan pada satu channel, disimpan pada config
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterKind {
+    /// tanpa filter
+    None,
+    /// [`ExponentialFilter`]
+    Exponential { period: usize },
+    /// [`MovingAverageFilter`]
+    MovingAverage { period: usize },
+    /// [`MedianFilter`]
+    Median { period: usize },
+    /// [`ButterworthFilter`], `cutoff` relatif terhadap sample rate (0.0 - 0.5)
+    Butterworth {
+        order: ButterworthOrder,
+        cutoff: Float,
+    },
+    /// [`LowPassFilter`] orde satu, `time_constant` dalam detik
+    TimeConstant { time_constant: Float },
+    /// [`ButterworthFilter`] dengan frekuensi cutoff dalam Hz
+    ButterworthHz {
+        order: ButterworthOrder,
+        cutoff_hz: Float,
+    },
+    /// [`KalmanFilter`], berbasis sample (tidak memakai period)
+    Kalman {
+        process_noise: Float,
+        measurement_noise: Float,
+    },
+    /// [`SavitzkyGolayFilter`]
+    SavitzkyGolay { window: usize, order: usize },
+}
+
+crate::macros::impl_serde_tagged!(FilterKind {
+    None,
+    Exponential { period: usize },
+    MovingAverage { period: usize },
+    Median { period: usize },
+    Butterworth { order: ButterworthOrder, cutoff: Float },
+    TimeConstant { time_constant: Float },
+    ButterworthHz { order: ButterworthOrder, cutoff_hz: Float },
+    Kalman { process_noise: Float, measurement_noise: Float },
+    SavitzkyGolay { window: usize, order: usize },
+});
+
+impl Default for FilterKind {
+    fn default() -> Self {
+        Self::Exponential { period: 9 }
+    }
+}
+
+impl std::fmt::Display for FilterKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::Exponential { period } => write!(f, "Exponential ({period} samples)"),
+            Self::MovingAverage { period } => write!(f, "Moving Average ({period} samples)"),
+            Self::Median { period } => write!(f, "Median ({period} samples)"),
+            Self::Butterworth { order, cutoff } => {
+                write!(f, "Butterworth {order:?} Order (cutoff {cutoff} fs)")
+            }
+            Self::TimeConstant { time_constant } => write!(f, "Low Pass (τ {time_constant} s)"),
+            Self::ButterworthHz { order, cutoff_hz } => {
+                write!(f, "Butterworth {order:?} Order (cutoff {cutoff_hz} Hz)")
+            }
+            Self::Kalman {
+                process_noise,
+                measurement_noise,
+            } => write!(f, "Kalman (q {process_noise}, r {measurement_noise})"),
+            Self::SavitzkyGolay { window, order } => {
+                write!(f, "Savitzky-Golay ({window} samples, order {order})")
+            }
+        }
+    }
+}
+
+impl FilterKind {
+    /// ## window length in samples of the sample-count based filter
+    /// return `None` for the other kinds
+    pub const fn period(self) -> Option<usize> {
+        match self {
+            Self::Exponential { period }
+            | Self::MovingAverage { period }
+            | Self::Median { period } => Some(period),
+            _ => None,
+        }
+    }
+
+    /// ## number of samples until the filter state settles, with sample `period` in seconds
+    /// filter FIR sepanjang window, filter IIR sampai respon sample lama < ~1e-4,
+    /// dibatasi [`FilterKind::MAX_WARMUP_LEN`]
+    pub fn warmup_len(self, period: Float) -> usize {
+        let settle = |samples: Float| match samples.is_finite() && samples > 0.0 {
+            true => (samples.ceil() as usize).min(Self::MAX_WARMUP_LEN),
+            false => Self::MAX_WARMUP_LEN,
+        };
+        match self {
+            Self::None => 0,
+            Self::Exponential { period } => settle(5.0 * (period + 1) as Float),
+            Self::MovingAverage { period } | Self::Median { period } => period,
+            Self::SavitzkyGolay { window, .. } => window,
+            Self::Butterworth { cutoff, .. } => settle(5.0 / cutoff),
+            Self::TimeConstant { time_constant } => settle(10.0 * time_constant / period),
+            Self::ButterworthHz { cutoff_hz, .. } => settle(5.0 / (cutoff_hz * period)),
+            Self::Kalman {
+                process_noise,
+                measurement_noise,
+            } => settle(5.0 * (measurement_noise / process_noise).sqrt()),
+        }
+    }
+
+    /// batas [`FilterKind::warmup_len`]
+    pub const MAX_WARMUP_LEN: usize = 2048;
+
+    /// ## create the filter of this kind
+    pub fn build<T: Numeric>(self) -> ChannelFilter<T> {
+        match self {
+            Self::None => ChannelFilter::None,
+            Self::Exponential { period } => {
+                ChannelFilter::Exponential(ExponentialFilter::new(period))
+            }
+            Self::MovingAverage { period } => {
+                ChannelFilter::MovingAverage(MovingAverageFilter::new(period))
+            }
+            Self::Median { period } => ChannelFilter::Median(MedianFilter::new(period)),
+            Self::Butterworth { order, cutoff } => {
+                ChannelFilter::Butterworth(ButterworthFilter::new(order, cutoff))
+            }
+            Self::TimeConstant { time_constant } => {
+                ChannelFilter::LowPass(LowPassFilter::new(time_constant))
+            }
+            Self::ButterworthHz { order, cutoff_hz } => {
+                ChannelFilter::Butterworth(ButterworthFilter::with_cutoff_hz(order, cutoff_hz))
+            }
+            Self::Kalman {
+                process_noise,
+                measurement_noise,
+            } => ChannelFilter::Kalman(KalmanFilter::new(process_noise, measurement_noise)),
+            Self::SavitzkyGolay { window, order } => {
+                ChannelFilter::SavitzkyGolay(SavitzkyGolayFilter::new(window, order))
+            }
+        }
+    }
+}
+
+impl FilterKind {
+    /// ## zero-phase filtering of recorded `values` at `time_ms` (forward-backward, filtfilt)
+    /// filter dijalankan maju lalu mundur, sehingga lag kedua arah saling menghilangkan
+    pub fn filtfilt<T: Numeric>(self, time_ms: &[i64], values: &[T]) -> Vec<T> {
+        let period = |idx: usize, prev: usize| match (time_ms.get(idx), time_ms.get(prev)) {
+            (Some(t), Some(p)) => (t - p).abs() as Float * 0.001,
+            _ => 0.0,
+        };
+        let mut filter = self.build::<T>();
+        let mut output = values
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| match idx {
+                0 => filter.next(*v),
+                _ => filter.next_with_period(*v, period(idx, idx - 1)),
+            })
+            .collect::<Vec<_>>();
+        filter.reset();
+        let last = output.len().saturating_sub(1);
+        output.iter_mut().enumerate().rev().for_each(|(idx, v)| {
+            *v = match idx == last {
+                true => filter.next(*v),
+                false => filter.next_with_period(*v, period(idx + 1, idx)),
+            }
+        });
+        output
+    }
+}
+
+/// serde channel filter pada config, hanya [`FilterKind`] tanpa state
+mod channel_kind {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{ChannelFilter, FilterKind};
+    use crate::Numeric;
+
+    /// format channel filter yang diterima dari format human-readable (toml, json)
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum FilterConfig {
+        Kind(FilterKind),
+        /// format config lama, `ExponentialFilter` dengan field `period` saja
+        Legacy {
+            period: usize,
+        },
+    }
+
+    pub fn serialize<S: Serializer, T: Numeric>(
+        filter: &ChannelFilter<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        filter.kind().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Numeric>(
+        deserializer: D,
+    ) -> Result<ChannelFilter<T>, D::Error> {
+        // format biner tidak mendukung untagged enum (`deserialize_any`)
+        let kind = match deserializer.is_human_readable() {
+            false => FilterKind::deserialize(deserializer)?,
+            true => match FilterConfig::deserialize(deserializer)? {
+                FilterConfig::Kind(kind) => kind,
+                FilterConfig::Legacy { period } => FilterKind::Exponential { period },
+            },
+        };
+        // config dapat diedit manual, period nol ditolak di sini agar tidak panic saat build
+        if kind.period() == Some(0) {
+            return Err(serde::de::Error::custom(format!(
+                "invalid filter `{kind}`, period should not be zero"
+            )));
+        }
+        Ok(kind.build())
+    }
+}
+
+/// # Filter Snapshot
+/// seluruh state [`DataFilter`] dan window [`DerivativeEstimator`] (torque) dari sesi yang
+/// sedang berjalan, untuk checkpoint dan melanjutkan sesi setelah crash
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct FilterSnapshot {
+    pub torque: ChannelFilter<NewtonMeter>,
+    pub horsepower: ChannelFilter<HorsePower>,
+    pub rpm_roda: ChannelFilter<RotationPerMinute>,
+    pub rpm_engine: ChannelFilter<RotationPerMinute>,
+    /// timestamp (ms) sample terakhir
+    pub time_ms: Option<i64>,
+    /// window `(timestamp ms, nilai)` estimator percepatan sudut roller
+    pub derivative: Vec<(i64, Float)>,
+}
+
+impl FilterSnapshot {
+    pub fn new(filter: &DataFilter, derivative: &DerivativeEstimator) -> Self {
+        Self {
+            torque: filter.torque.clone(),
+            horsepower: filter.horsepower.clone(),
+            rpm_roda: filter.rpm_roda.clone(),
+            rpm_engine: filter.rpm_engine.clone(),
+            time_ms: filter.time_ms,
+            derivative: derivative.samples().copied().collect(),
+        }
+    }
+
+    /// ## timestamp (ms) of the last sample before this snapshot
+    #[inline]
+    pub fn last_time_ms(&self) -> Option<i64> {
+        self.time_ms
+            .or(self.derivative.last().map(|(time_ms, _)| *time_ms))
+    }
+
+    /// ## restore the state into `filter` and `derivative`
+    /// timestamp state di-rebase pada sample pertama setelah restore,
+    /// lihat [`crate::DynoConfig::rebase_resumed`]
+    pub fn restore(self, filter: &mut DataFilter, derivative: &mut DerivativeEstimator) {
+        filter.resume_ms = self.last_time_ms();
+        filter.torque = self.torque;
+        filter.horsepower = self.horsepower;
+        filter.rpm_roda = self.rpm_roda;
+        filter.rpm_engine = self.rpm_engine;
+        filter.time_ms = self.time_ms;
+        derivative.restore(self.derivative);
+    }
+}
+
+/// # Channel Filter
+/// filter dari salah satu [`FilterKind`] beserta state-nya
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(bound(
+    serialize = "T: serde::Serialize",
+    deserialize = "T: serde::Deserialize<'de>"
+))]
+pub enum ChannelFilter<T: Numeric> {
+    None,
+    Exponential(ExponentialFilter<T>),
+    MovingAverage(MovingAverageFilter<T>),
+    Median(MedianFilter<T>),
+    Butterworth(ButterworthFilter<T>),
+    LowPass(LowPassFilter<T>),
+    Kalman(KalmanFilter<T>),
+    SavitzkyGolay(SavitzkyGolayFilter<T>),
+}
+
+impl<T: Numeric> Default for ChannelFilter<T> {
+    fn default() -> Self {
+        FilterKind::default().build()
+    }
+}
+
+impl<T: Numeric> ChannelFilter<T> {
+    /// ## configuration of this filter
+    pub fn kind(&self) -> FilterKind {
+        match self {
+            Self::None => FilterKind::None,
+            Self::Exponential(f) => FilterKind::Exponential { period: f.period() },
+            Self::MovingAverage(f) => FilterKind::MovingAverage { period: f.period },
+            Self::Median(f) => FilterKind::Median { period: f.period },
+            Self::Butterworth(f) => match f.cutoff_hz {
+                Some(cutoff_hz) => FilterKind::ButterworthHz {
+                    order: f.order,
+                    cutoff_hz,
+                },
+                None => FilterKind::Butterworth {
+                    order: f.order,
+                    cutoff: f.cutoff,
+                },
+            },
+            Self::LowPass(f) => FilterKind::TimeConstant {
+                time_constant: f.time_constant,
+            },
+            Self::Kalman(f) => FilterKind::Kalman {
+                process_noise: f.process_noise,
+                measurement_noise: f.measurement_noise,
+            },
+            Self::SavitzkyGolay(f) => FilterKind::SavitzkyGolay {
+                window: f.window,
+                order: f.order,
+            },
+        }
+    }
+}
+
+impl<T: Numeric> Filter<T> for ChannelFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        match self {
+            Self::None => input,
+            Self::Exponential(f) => f.next(input),
+            Self::MovingAverage(f) => f.next(input),
+            Self::Median(f) => f.next(input),
+            Self::Butterworth(f) => f.next(input),
+            Self::LowPass(f) => f.next(input),
+            Self::Kalman(f) => f.next(input),
+            Self::SavitzkyGolay(f) => f.next(input),
+        }
+    }
+
+    fn next_with_period(&mut self, input: T, period: Float) -> T {
+        match self {
+            Self::Butterworth(f) => f.next_with_period(input, period),
+            Self::LowPass(f) => f.next_with_period(input, period),
+            _ => self.next(input),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self {
+            Self::None => {}
+            Self::Exponential(f) => f.reset(),
+            Self::MovingAverage(f) => Filter::reset(f),
+            Self::Median(f) => Filter::reset(f),
+            Self::Butterworth(f) => Filter::reset(f),
+            Self::LowPass(f) => Filter::reset(f),
+            Self::Kalman(f) => Filter::reset(f),
+            Self::SavitzkyGolay(f) => Filter::reset(f),
+        }
     }
 }
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "ExponentialState<T>", into = "ExponentialState<T>")]
+#[serde(bound(
+    serialize = "T: serde::Serialize",
+    deserialize = "T: serde::Deserialize<'de>"
+))]
 pub struct ExponentialFilter<T: Numeric> {
     period: usize,
-    #[serde(default)]
-    #[serde(skip)]
     k: T,
-    #[serde(default)]
-    #[serde(skip)]
     current: T,
-    #[serde(default)]
-    #[serde(skip)]
     is_new: bool,
 }
 
+/// bentuk serialisasi [`ExponentialFilter`], `k` dihitung ulang dari `period`
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+struct ExponentialState<T: Numeric> {
+    period: usize,
+    current: T,
+    is_new: bool,
+}
+
+impl<T: Numeric> Default for ExponentialState<T> {
+    fn default() -> Self {
+        let ExponentialFilter {
+            period,
+            current,
+            is_new,
+            ..
+        } = ExponentialFilter::<T>::default();
+        Self {
+            period,
+            current,
+            is_new,
+        }
+    }
+}
+
+impl<T: Numeric> TryFrom<ExponentialState<T>> for ExponentialFilter<T> {
+    type Error = &'static str;
+
+    fn try_from(value: ExponentialState<T>) -> Result<Self, Self::Error> {
+        if value.period == 0 {
+            return Err("period should not be zero");
+        }
+        Ok(Self {
+            current: value.current,
+            is_new: value.is_new,
+            ..Self::new(value.period)
+        })
+    }
+}
+
+impl<T: Numeric> From<ExponentialFilter<T>> for ExponentialState<T> {
+    fn from(value: ExponentialFilter<T>) -> Self {
+        Self {
+            period: value.period,
+            current: value.current,
+            is_new: value.is_new,
+        }
+    }
+}
+
 impl<T: Numeric> Default for ExponentialFilter<T> {
     fn default() -> Self {
         Self::new(9)
@@ -94,6 +600,488 @@ impl<T: Numeric> ExponentialFilter<T> {
     }
 }
 
+impl<T: Numeric> Filter<T> for ExponentialFilter<T> {
+    #[inline]
+    fn next(&mut self, input: T) -> T {
+        ExponentialFilter::next(self, input)
+    }
+    #[inline]
+    fn reset(&mut self) {
+        ExponentialFilter::reset(self)
+    }
+}
+
+/// # Simple Moving Average
+/// rata-rata `period` sample terakhir
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct MovingAverageFilter<T: Numeric> {
+    period: usize,
+    #[serde(default)]
+    window: VecDeque<T>,
+    #[serde(default)]
+    sum: Float,
+}
+
+impl<T: Numeric> MovingAverageFilter<T> {
+    pub fn new(period: usize) -> Self {
+        let period = Ord::max(period, 1);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
+    }
+
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: Numeric> Filter<T> for MovingAverageFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        if self.window.len() >= self.period {
+            if let Some(old) = self.window.pop_front() {
+                self.sum -= old.to_float();
+            }
+        }
+        self.window.push_back(input);
+        self.sum += input.to_float();
+        T::from_float(self.sum / self.window.len() as Float)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.window.clear();
+        self.sum = 0.0;
+    }
+}
+
+/// # Median Filter
+/// median `period` sample terakhir, meredam spike tanpa menggeser step
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct MedianFilter<T: Numeric> {
+    period: usize,
+    #[serde(default)]
+    window: VecDeque<T>,
+    /// buffer kerja untuk seleksi median, selalu kosong di antara pemanggilan `next`
+    #[serde(skip)]
+    scratch: Vec<Float>,
+}
+
+impl<T: Numeric> MedianFilter<T> {
+    pub fn new(period: usize) -> Self {
+        let period = Ord::max(period, 1);
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            scratch: Vec::with_capacity(period),
+        }
+    }
+
+    #[inline]
+    pub const fn period(&self) -> usize {
+        self.period
+    }
+}
+
+impl<T: Numeric> Filter<T> for MedianFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        if self.window.len() >= self.period {
+            self.window.pop_front();
+        }
+        self.window.push_back(input);
+        self.scratch
+            .extend(self.window.iter().map(|v| v.to_float()));
+        let median = super::quality::median(&mut self.scratch);
+        self.scratch.clear();
+        T::from_float(median)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// satu section biquad direct form I
+#[derive(serde::Deserialize, serde::Serialize, Debug, Default, Clone, Copy, PartialEq)]
+struct Biquad {
+    b: [Float; 3],
+    a: [Float; 2],
+    x: [Float; 2],
+    y: [Float; 2],
+    initialized: bool,
+}
+
+impl Biquad {
+    /// low-pass dengan bilinear transform, `cutoff` relatif terhadap sample rate
+    fn low_pass(cutoff: Float, q: Float) -> Self {
+        let mut biquad = Self::default();
+        biquad.tune(cutoff, q);
+        biquad
+    }
+
+    /// hitung ulang koefisien tanpa mengubah state
+    fn tune(&mut self, cutoff: Float, q: Float) {
+        let k = (std::f64::consts::PI as Float * cutoff).tan();
+        let norm = 1.0 / (1.0 + k / q + k * k);
+        let b0 = k * k * norm;
+        self.b = [b0, 2.0 * b0, b0];
+        self.a = [2.0 * (k * k - 1.0) * norm, (1.0 - k / q + k * k) * norm];
+    }
+
+    fn next(&mut self, input: Float) -> Float {
+        if !self.initialized {
+            // mulai dari kondisi steady state agar tidak ada transien awal
+            self.initialized = true;
+            self.x = [input; 2];
+            self.y = [input; 2];
+        }
+        let [b0, b1, b2] = self.b;
+        let [a1, a2] = self.a;
+        let output = b0 * input + b1 * self.x[0] + b2 * self.x[1] - a1 * self.y[0] - a2 * self.y[1];
+        self.x = [input, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+/// # Butterworth low-pass filter
+/// cascade biquad orde 2 atau 4, `cutoff` relatif terhadap sample rate (0.0 - 0.5).
+/// jika `cutoff_hz` diisi, `cutoff` dihitung ulang dari period setiap sample
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct ButterworthFilter<T: Numeric> {
+    order: ButterworthOrder,
+    cutoff: Float,
+    #[serde(default)]
+    cutoff_hz: Option<Float>,
+    #[serde(default)]
+    sections: Vec<Biquad>,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: Numeric> ButterworthFilter<T> {
+    pub fn new(order: ButterworthOrder, cutoff: Float) -> Self {
+        let mut filter = Self {
+            order,
+            cutoff: Self::relative_cutoff(cutoff),
+            cutoff_hz: None,
+            sections: Vec::new(),
+            _marker: PhantomData,
+        };
+        Filter::reset(&mut filter);
+        filter
+    }
+
+    /// ## butterworth filter with cutoff frequency in Hz
+    /// sebelum period sample diketahui, filter meneruskan input (cutoff 0.499 fs)
+    pub fn with_cutoff_hz(order: ButterworthOrder, cutoff_hz: Float) -> Self {
+        Self {
+            cutoff_hz: Some(cutoff_hz.abs()),
+            ..Self::new(order, 0.499)
+        }
+    }
+
+    #[inline]
+    fn relative_cutoff(cutoff: Float) -> Float {
+        ternary!((cutoff.is_normal())?(cutoff.clamp(1e-6, 0.499)): (0.499))
+    }
+
+    #[inline]
+    fn section_q(&self) -> impl Iterator<Item = Float> {
+        // Q setiap section dari posisi pole, `1 / (2 sin((2k + 1) π / 2n))`
+        let n = match self.order {
+            ButterworthOrder::Second => 2,
+            ButterworthOrder::Fourth => 4,
+        };
+        let pi = std::f64::consts::PI as Float;
+        (0..n / 2).map(move |k| 1.0 / (2.0 * ((2 * k + 1) as Float * pi / (2 * n) as Float).sin()))
+    }
+
+    #[inline]
+    pub const fn order(&self) -> ButterworthOrder {
+        self.order
+    }
+
+    #[inline]
+    pub const fn cutoff(&self) -> Float {
+        self.cutoff
+    }
+
+    #[inline]
+    pub const fn cutoff_hz(&self) -> Option<Float> {
+        self.cutoff_hz
+    }
+}
+
+impl<T: Numeric> Filter<T> for ButterworthFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        if self.sections.is_empty() {
+            // deserialisasi tanpa state
+            Filter::reset(self);
+        }
+        let output = self
+            .sections
+            .iter_mut()
+            .fold(input.to_float(), |value, section| section.next(value));
+        T::from_float(output)
+    }
+
+    fn next_with_period(&mut self, input: T, period: Float) -> T {
+        if let Some(cutoff_hz) = self.cutoff_hz {
+            let cutoff = Self::relative_cutoff(cutoff_hz * period);
+            if (cutoff - self.cutoff).abs() > 1e-12 {
+                self.cutoff = cutoff;
+                let q = self.section_q().collect::<Vec<_>>();
+                for (section, q) in self.sections.iter_mut().zip(q) {
+                    section.tune(cutoff, q);
+                }
+            }
+        }
+        self.next(input)
+    }
+
+    fn reset(&mut self) {
+        let cutoff = self.cutoff;
+        self.sections = self
+            .section_q()
+            .map(|q| Biquad::low_pass(cutoff, q))
+            .collect();
+    }
+}
+
+/// # Low-pass filter orde satu
+/// `y += α (x - y)` dengan `α = 1 - e^(-Δt / τ)` dihitung dari period setiap sample,
+/// sehingga respon filter sama untuk sample rate yang berbeda
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct LowPassFilter<T: Numeric> {
+    /// time constant τ (detik)
+    time_constant: Float,
+    /// period sample terakhir (detik)
+    #[serde(default)]
+    period: Float,
+    #[serde(default)]
+    current: T,
+    #[serde(default)]
+    initialized: bool,
+}
+
+impl<T: Numeric> LowPassFilter<T> {
+    pub fn new(time_constant: Float) -> Self {
+        Self {
+            time_constant: ternary!((time_constant.is_finite())?(time_constant.abs()): (0.0)),
+            period: 0.0,
+            current: T::default(),
+            initialized: false,
+        }
+    }
+
+    /// ## low-pass filter with cutoff frequency in Hz, `τ = 1 / (2π fc)`
+    pub fn from_cutoff_hz(cutoff_hz: Float) -> Self {
+        Self::new(1.0 / (2.0 * std::f64::consts::PI as Float * cutoff_hz))
+    }
+
+    #[inline]
+    pub const fn time_constant(&self) -> Float {
+        self.time_constant
+    }
+
+    /// ## smoothing factor for a sample `period` seconds
+    #[inline]
+    pub fn alpha(&self, period: Float) -> Float {
+        match self.time_constant > 0.0 {
+            true => 1.0 - (-period.max(0.0) / self.time_constant).exp(),
+            false => 1.0,
+        }
+    }
+}
+
+impl<T: Numeric> Filter<T> for LowPassFilter<T> {
+    /// menggunakan period sample sebelumnya, tanpa period input diteruskan
+    fn next(&mut self, input: T) -> T {
+        if !self.initialized || self.period <= 0.0 {
+            self.initialized = true;
+            self.current = input;
+            return input;
+        }
+        let current = self.current.to_float();
+        let output = current + self.alpha(self.period) * (input.to_float() - current);
+        self.current = T::from_float(output);
+        self.current
+    }
+
+    #[inline]
+    fn next_with_period(&mut self, input: T, period: Float) -> T {
+        self.period = period;
+        self.next(input)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.period = 0.0;
+        self.current = T::default();
+        self.initialized = false;
+    }
+}
+
+/// # Kalman Filter
+/// model 1-D konstan percepatan dengan state `[nilai, kecepatan, percepatan]` per sample.
+/// langkah waktu model adalah satu sample, `period` pada [`Filter::next_with_period`] diabaikan,
+/// sehingga `process_noise` perlu di-tuning ulang jika sample rate berubah.
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct KalmanFilter<T: Numeric> {
+    /// varians perubahan percepatan (jerk) setiap sample
+    process_noise: Float,
+    /// varians noise pengukuran
+    measurement_noise: Float,
+    #[serde(default)]
+    state: [Float; 3],
+    #[serde(default)]
+    covariance: [[Float; 3]; 3],
+    #[serde(default)]
+    initialized: bool,
+    #[serde(skip)]
+    _marker: PhantomData<T>,
+}
+
+impl<T: Numeric> KalmanFilter<T> {
+    pub fn new(process_noise: Float, measurement_noise: Float) -> Self {
+        Self {
+            process_noise: process_noise.abs(),
+            measurement_noise: ternary!((measurement_noise.is_normal())?(measurement_noise.abs()): (1.0)),
+            state: [0.0; 3],
+            covariance: [[0.0; 3]; 3],
+            initialized: false,
+            _marker: PhantomData,
+        }
+    }
+
+    #[inline]
+    pub const fn process_noise(&self) -> Float {
+        self.process_noise
+    }
+
+    #[inline]
+    pub const fn measurement_noise(&self) -> Float {
+        self.measurement_noise
+    }
+}
+
+impl<T: Numeric> Filter<T> for KalmanFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        let z = input.to_float();
+        if !self.initialized || !self.state[0].is_finite() {
+            self.initialized = true;
+            self.state = [z, 0.0, 0.0];
+            let r = self.measurement_noise;
+            self.covariance = [[r, 0.0, 0.0], [0.0, r, 0.0], [0.0, 0.0, r]];
+            return input;
+        }
+        // predict, F = [[1, 1, 1/2], [0, 1, 1], [0, 0, 1]]
+        const F: [[Float; 3]; 3] = [[1.0, 1.0, 0.5], [0.0, 1.0, 1.0], [0.0, 0.0, 1.0]];
+        const G: [Float; 3] = [0.5, 1.0, 1.0];
+        let [x, v, a] = self.state;
+        self.state = [x + v + 0.5 * a, v + a, a];
+        let p = self.covariance;
+        let mut fp = [[0.0; 3]; 3];
+        for (row, f) in fp.iter_mut().zip(F) {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| f[k] * p[k][j]).sum();
+            }
+        }
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| fp[i][k] * F[j][k]).sum::<Float>()
+                    + self.process_noise * G[i] * G[j];
+            }
+        }
+
+        // update, H = [1, 0, 0]
+        let p = self.covariance;
+        let s = p[0][0] + self.measurement_noise;
+        if s <= 0.0 || !s.is_finite() {
+            return T::from_float(self.state[0]);
+        }
+        let gain = [p[0][0] / s, p[1][0] / s, p[2][0] / s];
+        let innovation = z - self.state[0];
+        for (i, row) in self.covariance.iter_mut().enumerate() {
+            self.state[i] += gain[i] * innovation;
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell -= gain[i] * p[0][j];
+            }
+        }
+        T::from_float(self.state[0])
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.state = [0.0; 3];
+        self.covariance = [[0.0; 3]; 3];
+        self.initialized = false;
+    }
+}
+
+/// # Savitzky–Golay Filter
+/// polinomial least-squares orde `order` (1 - 3) dari `window` sample terakhir,
+/// dievaluasi di sample terakhir
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, PartialEq)]
+pub struct SavitzkyGolayFilter<T: Numeric> {
+    window: usize,
+    order: usize,
+    #[serde(default)]
+    samples: VecDeque<T>,
+}
+
+impl<T: Numeric> SavitzkyGolayFilter<T> {
+    pub fn new(window: usize, order: usize) -> Self {
+        let order = order.clamp(1, 3);
+        let window = Ord::max(window, order + 1);
+        Self {
+            window,
+            order,
+            samples: VecDeque::with_capacity(window),
+        }
+    }
+
+    #[inline]
+    pub const fn window(&self) -> usize {
+        self.window
+    }
+
+    #[inline]
+    pub const fn order(&self) -> usize {
+        self.order
+    }
+}
+
+impl<T: Numeric> Filter<T> for SavitzkyGolayFilter<T> {
+    fn next(&mut self, input: T) -> T {
+        if self.samples.len() >= self.window {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(input);
+        let len = self.samples.len();
+        if len < 2 {
+            return input;
+        }
+        let rows = self
+            .samples
+            .iter()
+            .enumerate()
+            .map(|(idx, v)| ((idx + 1) as Float - len as Float, v.to_float()));
+        super::derivative::poly_coefficient(rows, self.order.min(len - 1), 0)
+            .filter(|v| v.is_finite())
+            .map_or(input, T::from_float)
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.samples.clear();
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,4 +1115,282 @@ mod tests {
         ema.reset();
         assert_eq!(ema.next(4.0), 4.0);
     }
+
+    fn step_response<F: Filter<Float>>(mut filter: F) -> Vec<Float> {
+        (0..200)
+            .map(|i| filter.next(ternary!((i < 20)?(1.0): (5.0))))
+            .collect()
+    }
+
+    #[test]
+    fn test_filters_settle() {
+        for kind in [
+            FilterKind::None,
+            FilterKind::Exponential { period: 5 },
+            FilterKind::MovingAverage { period: 5 },
+            FilterKind::Median { period: 5 },
+            FilterKind::Butterworth {
+                order: ButterworthOrder::Second,
+                cutoff: 0.1,
+            },
+            FilterKind::Butterworth {
+                order: ButterworthOrder::Fourth,
+                cutoff: 0.1,
+            },
+            FilterKind::Kalman {
+                process_noise: 1e-3,
+                measurement_noise: 1.0,
+            },
+            FilterKind::SavitzkyGolay {
+                window: 9,
+                order: 2,
+            },
+        ] {
+            let output = step_response(kind.build());
+            // nilai awal tidak ada transien, nilai akhir mengikuti input
+            assert!((output[0] - 1.0).abs() < 1e-9, "{kind}: {}", output[0]);
+            assert!((output[199] - 5.0).abs() < 1e-3, "{kind}: {}", output[199]);
+        }
+    }
+
+    #[test]
+    fn test_filtfilt_zero_phase() {
+        let bump = (0..100)
+            .map(|i| (-((i as Float - 50.0) / 8.0).powi(2)).exp())
+            .collect::<Vec<_>>();
+        let peak = |values: &[Float]| {
+            values
+                .iter()
+                .enumerate()
+                .fold(
+                    (0, Float::MIN),
+                    |max, (idx, v)| ternary!((*v > max.1)?((idx, *v)): (max)),
+                )
+                .0
+        };
+        let kind = FilterKind::Exponential { period: 9 };
+        let mut causal = kind.build();
+        let lagged = bump.iter().map(|v| causal.next(*v)).collect::<Vec<_>>();
+        assert!(peak(&lagged) > 52);
+        let time = (0..100).map(|i| i * 50).collect::<Vec<i64>>();
+        assert_eq!(peak(&kind.filtfilt(&time, &bump)), 50);
+    }
+
+    #[test]
+    fn test_median_spike() {
+        let mut median = MedianFilter::new(3);
+        let output = [1.0, 1.0, 100.0, 1.0, 1.0].map(|v| median.next(v));
+        assert_eq!(output, [1.0; 5]);
+    }
+
+    #[test]
+    fn test_moving_average() {
+        let mut sma = MovingAverageFilter::new(3);
+        let output = [3.0, 6.0, 9.0, 12.0].map(|v| sma.next(v));
+        assert_eq!(output, [3.0, 4.5, 6.0, 9.0]);
+        Filter::<Float>::reset(&mut sma);
+        assert_eq!(sma.next(1.0), 1.0);
+    }
+
+    #[test]
+    fn test_butterworth_attenuation() {
+        // sinyal di atas cutoff diredam, orde 4 lebih curam
+        let amplitude = |order| {
+            let mut filter = ButterworthFilter::new(order, 0.05);
+            (0..400)
+                .map(|i| {
+                    let x = (i as Float * 0.4 * std::f64::consts::PI as Float).sin();
+                    filter.next(x)
+                })
+                .skip(200)
+                .fold(0.0 as Float, |max, v| max.max(v.abs()))
+        };
+        let second = amplitude(ButterworthOrder::Second);
+        let fourth = amplitude(ButterworthOrder::Fourth);
+        assert!(second < 0.05, "{second}");
+        assert!(fourth < second * 0.1, "{fourth}");
+    }
+
+    #[test]
+    fn test_channel_filter_serde() {
+        let filter = DataFilter {
+            torque: FilterKind::Butterworth {
+                order: ButterworthOrder::Fourth,
+                cutoff: 0.1,
+            }
+            .build(),
+            ..Default::default()
+        };
+        let toml = toml::to_string(&filter).unwrap();
+        let decoded: DataFilter = toml::from_str(&toml).unwrap();
+        assert_eq!(decoded, filter);
+
+        // config lama, exponential filter dengan period saja
+        let legacy = r#"
+            [torque]
+            period = 4
+            [horsepower]
+            period = 2
+            [rpm_roda]
+            period = 100
+            [rpm_engine]
+            type = "Median"
+            period = 5
+        "#;
+        let decoded: DataFilter = toml::from_str(legacy).unwrap();
+        assert_eq!(decoded.torque.kind(), FilterKind::Exponential { period: 4 });
+        assert_eq!(decoded.rpm_engine.kind(), FilterKind::Median { period: 5 });
+
+        // period nol dari config yang diedit manual ditolak
+        for zero in [
+            legacy.replace("period = 4", "period = 0"),
+            legacy.replace("period = 5", "period = 0"),
+            legacy
+                .replace(r#"type = "Median""#, r#"type = "MovingAverage""#)
+                .replace("period = 5", "period = 0"),
+        ] {
+            assert!(toml::from_str::<DataFilter>(&zero).is_err(), "{zero}");
+        }
+        assert!(toml::from_str::<ExponentialFilter<Float>>("period = 0").is_err());
+    }
+
+    #[test]
+    fn test_exponential_serde() {
+        // config tanpa state, `k` dihitung ulang dari period
+        let mut ema: ExponentialFilter<Float> = toml::from_str("period = 3").unwrap();
+        assert_eq!(ema.next(2.0), 2.0);
+        assert_eq!(ema.next(5.0), 3.5);
+
+        let toml = toml::to_string(&ema).unwrap();
+        let mut decoded: ExponentialFilter<Float> = toml::from_str(&toml).unwrap();
+        assert_eq!(decoded, ema);
+        assert_eq!(decoded.next(1.0), ema.next(1.0));
+    }
+
+    #[test]
+    fn test_snapshot_resume() {
+        use crate::{CompresedSaver, DerivativeKind};
+
+        let mut filter = DataFilter {
+            torque: FilterKind::Kalman {
+                process_noise: 1e-2,
+                measurement_noise: 1.0,
+            }
+            .build(),
+            horsepower: FilterKind::Butterworth {
+                order: ButterworthOrder::Fourth,
+                cutoff: 0.1,
+            }
+            .build(),
+            rpm_roda: FilterKind::SavitzkyGolay {
+                window: 7,
+                order: 2,
+            }
+            .build(),
+            rpm_engine: FilterKind::Median { period: 5 }.build(),
+            ..Default::default()
+        };
+        let mut derivative = DerivativeEstimator::new(DerivativeKind::default());
+        let input = |i: usize| (i as Float * 0.3).sin() * 10.0 + i as Float;
+        let feed = |filter: &mut DataFilter, derivative: &mut DerivativeEstimator, i: usize| {
+            let x = input(i);
+            [
+                filter.torque.next(NewtonMeter(x)).value(),
+                filter.horsepower.next(HorsePower(x)).value(),
+                filter.rpm_roda.next(RotationPerMinute(x)).value(),
+                filter.rpm_engine.next(RotationPerMinute(x)).value(),
+                derivative.next(i as i64 * 50, x),
+            ]
+        };
+        (0..50).for_each(|i| {
+            feed(&mut filter, &mut derivative, i);
+        });
+
+        // checkpoint di tengah sesi melalui toml dan format biner,
+        // dilanjutkan tanpa diskontinuitas
+        let snapshot = FilterSnapshot::new(&filter, &derivative);
+        let toml = toml::to_string(&snapshot).unwrap();
+        let from_toml: FilterSnapshot = toml::from_str(&toml).unwrap();
+        assert_eq!(from_toml, snapshot);
+        let from_bin = FilterSnapshot::decompress(snapshot.compress().unwrap()).unwrap();
+        assert_eq!(from_bin, snapshot);
+
+        let mut resumed = DataFilter::default();
+        let mut resumed_derivative = DerivativeEstimator::new(DerivativeKind::default());
+        from_bin.restore(&mut resumed, &mut resumed_derivative);
+        for i in 50..60 {
+            let expected = feed(&mut filter, &mut derivative, i);
+            let actual = feed(&mut resumed, &mut resumed_derivative, i);
+            for (e, a) in expected.iter().zip(actual) {
+                assert!((e - a).abs() < 1e-9, "{i}: {e} != {a}");
+            }
+        }
+
+        // config tidak menyimpan state
+        let config: DataFilter = toml::from_str(&toml::to_string(&filter).unwrap()).unwrap();
+        assert_eq!(config.torque.kind(), filter.torque.kind());
+        assert_ne!(config.torque, filter.torque);
+        let config = DataFilter::decompress(filter.compress().unwrap()).unwrap();
+        assert_eq!(config.horsepower.kind(), filter.horsepower.kind());
+        assert_ne!(config.horsepower, filter.horsepower);
+    }
+
+    /// respon step pada t = `at` detik dengan period sample `period` detik
+    fn step_at(kind: FilterKind, period: Float, at: Float) -> Float {
+        let mut filter = kind.build::<Float>();
+        filter.next(0.0);
+        let steps = (at / period).round() as usize;
+        (0..steps).fold(0.0, |_, _| filter.next_with_period(1.0, period))
+    }
+
+    #[test]
+    fn test_time_based_sample_rate() {
+        let kind = FilterKind::TimeConstant { time_constant: 0.5 };
+        let expected = 1.0 - (-1.0 / 0.5 as Float).exp();
+        for period in [0.2, 0.05, 0.01] {
+            let value = step_at(kind, period, 1.0);
+            assert!((value - expected).abs() < 1e-9, "{period}: {value}");
+        }
+
+        // sample-count filter bergantung pada sample rate, butterworth Hz tidak
+        let count = FilterKind::Exponential { period: 5 };
+        assert!((step_at(count, 0.2, 1.0) - step_at(count, 0.05, 1.0)).abs() > 0.1);
+        let hz = FilterKind::ButterworthHz {
+            order: ButterworthOrder::Second,
+            cutoff_hz: 0.5,
+        };
+        let (slow, fast) = (step_at(hz, 0.1, 1.0), step_at(hz, 0.02, 1.0));
+        assert!((slow - fast).abs() < 0.05, "{slow} != {fast}");
+    }
+
+    #[test]
+    fn test_adaptive_period() {
+        let mut filter = DataFilter {
+            torque: FilterKind::TimeConstant { time_constant: 0.2 }.build(),
+            ..Default::default()
+        };
+        assert_eq!(filter.period(1000), None);
+        assert_eq!(filter.period(1200), Some(0.2));
+        assert_eq!(filter.period(1200), None);
+        filter.reset();
+        assert_eq!(filter.period(1250), None);
+
+        for kind in [
+            FilterKind::TimeConstant { time_constant: 0.2 },
+            FilterKind::ButterworthHz {
+                order: ButterworthOrder::Fourth,
+                cutoff_hz: 2.0,
+            },
+        ] {
+            assert_eq!(kind.build::<Float>().kind(), kind);
+        }
+
+        // period berubah di tengah sesi
+        let mut low_pass = LowPassFilter::<Float>::new(0.2);
+        assert_eq!(low_pass.next_with_period(0.0, 0.2), 0.0);
+        let a = low_pass.next_with_period(1.0, 0.2);
+        assert!((a - low_pass.alpha(0.2)).abs() < 1e-12);
+        let b = low_pass.next_with_period(1.0, 0.05);
+        assert!((b - (a + low_pass.alpha(0.05) * (1.0 - a))).abs() < 1e-12);
+    }
 }
@@ -1,20 +1,44 @@
 pub mod buffer;
 pub mod data_buffer;
+pub mod derivative;
 pub mod filter;
 pub mod infomotor;
+pub mod quality;
+pub mod resample;
+pub mod serial;
+pub mod session;
+pub mod statistics;
 pub use filter::ExponentialFilter;
 
 pub mod prelude {
     pub use super::buffer::*;
     pub use super::data_buffer::*;
+    pub use super::derivative::*;
+    pub use super::filter::*;
     pub use super::infomotor::*;
-    pub use super::SerialData;
+    pub use super::quality::*;
+    pub use super::resample::*;
+    pub use super::serial::{
+        crc16_ccitt, Handshake, ProtocolVersion, SerialFrameDecoder, SerialMessage, SerialPayload,
+    };
+    pub use super::session::*;
+    pub use super::statistics::*;
+    pub use super::{SerialData, SerialDataV2};
 }
 
 // macro `repr(C)` agar dapat merepresentasikan struct ini sebagai
 // struct yang sama pada bahasa pemrograman C
 #[repr(C)]
-#[derive(Debug, Default, Clone, Copy, serde::Deserialize, derive_more::Display)]
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+)]
 #[display(fmt = r"SerialData {{ 
     period: {period},
     enc_max: {pulse_enc_max},
@@ -33,16 +57,112 @@ pub struct SerialData {
 }
 
 impl SerialData {
-    pub const SIZE: usize = ::core::mem::size_of::<SerialData>();
+    /// ukuran `SerialData` pada wire (6 field x 4 bytes, little-endian)
+    pub const SIZE: usize = 6 * ::core::mem::size_of::<u32>();
     pub const DELIM: u8 = b'\n';
 
     #[inline(always)]
-    // konversi bytes (array of byte) secara menyamakan memory layout
-    // memvalidasi apakah ukuran bytes sama dengan ukuran  data struct
+    // konversi bytes (array of byte) dengan membaca setiap field secara eksplisit
+    // sebagai little-endian, sehingga tidak bergantung pada endianness dan alignment host.
+    // memvalidasi apakah ukuran bytes sama dengan ukuran data pada wire
     pub fn from_bytes(bytes: &'_ [u8]) -> Option<Self> {
         if bytes.len() != Self::SIZE {
             return None;
         }
-        Some(unsafe { std::ptr::read::<Self>(bytes.as_ptr() as *const _) })
+        let mut fields = bytes.chunks_exact(4).map(|c| [c[0], c[1], c[2], c[3]]);
+        Some(Self {
+            period: u32::from_le_bytes(fields.next()?),
+            pulse_enc_max: u32::from_le_bytes(fields.next()?),
+            pulse_enc: u32::from_le_bytes(fields.next()?),
+            pulse_enc_z: u32::from_le_bytes(fields.next()?),
+            pulse_rpm: u32::from_le_bytes(fields.next()?),
+            temperature: f32::from_le_bytes(fields.next()?),
+        })
+    }
+
+    /// ## encode into little-endian bytes, the inverse of [`SerialData::from_bytes`]
+    /// ```
+    /// use dyno_core::SerialData;
+    /// let data = SerialData { period: 200, temperature: 42.5, ..Default::default() };
+    /// let bytes = data.to_bytes();
+    /// assert_eq!(&bytes[..4], &200u32.to_le_bytes());
+    /// assert_eq!(SerialData::from_bytes(&bytes).unwrap().temperature, 42.5);
+    /// ```
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        [
+            self.period.to_le_bytes(),
+            self.pulse_enc_max.to_le_bytes(),
+            self.pulse_enc.to_le_bytes(),
+            self.pulse_enc_z.to_le_bytes(),
+            self.pulse_rpm.to_le_bytes(),
+            self.temperature.to_le_bytes(),
+        ]
+        .iter()
+        .zip(bytes.chunks_exact_mut(4))
+        .for_each(|(field, chunk)| chunk.copy_from_slice(field));
+        bytes
+    }
+
+    /// ## encode into a framed and checksummed bytes, see [`serial`] module
+    #[inline]
+    pub fn to_frame(&self) -> Vec<u8> {
+        serial::SerialPayload::V1(*self).to_frame()
+    }
+}
+
+/// # SerialData protocol revision 2
+/// layout sama dengan [`SerialData`] ditambah channel temperature kedua dan load cell
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    derive_more::Display,
+)]
+#[display(fmt = r"SerialDataV2 {{ 
+    {base},
+    temp_2: {temperature_2},
+    load_cell: {load_cell}
+}}")]
+pub struct SerialDataV2 {
+    pub base: SerialData,
+    pub temperature_2: f32,
+    pub load_cell: f32,
+}
+
+impl SerialDataV2 {
+    pub const SIZE: usize = SerialData::SIZE + 2 * ::core::mem::size_of::<f32>();
+
+    #[inline(always)]
+    pub fn from_bytes(bytes: &'_ [u8]) -> Option<Self> {
+        if bytes.len() != Self::SIZE {
+            return None;
+        }
+        let (base, ext) = bytes.split_at(SerialData::SIZE);
+        Some(Self {
+            base: SerialData::from_bytes(base)?,
+            temperature_2: f32::from_le_bytes([ext[0], ext[1], ext[2], ext[3]]),
+            load_cell: f32::from_le_bytes([ext[4], ext[5], ext[6], ext[7]]),
+        })
+    }
+
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0u8; Self::SIZE];
+        bytes[..SerialData::SIZE].copy_from_slice(&self.base.to_bytes());
+        bytes[SerialData::SIZE..SerialData::SIZE + 4]
+            .copy_from_slice(&self.temperature_2.to_le_bytes());
+        bytes[SerialData::SIZE + 4..].copy_from_slice(&self.load_cell.to_le_bytes());
+        bytes
+    }
+
+    #[inline]
+    pub fn to_frame(&self) -> Vec<u8> {
+        serial::SerialPayload::V2(*self).to_frame()
     }
 }
@@ -0,0 +1,436 @@
+//! # Sample Quality
+//! bitmask kualitas setiap sample, validasi pulse encoder menggunakan Z index
+//! dan deteksi outlier (Hampel filter) yang hanya menandai sample tanpa mengubah nilainya.
+use crate::{convertions::prelude::*, Float, Numeric};
+
+use super::SerialData;
+
+/// bitmask kualitas sample, `SampleQuality::OK` jika tidak ada masalah
+#[derive(
+    serde::Deserialize, serde::Serialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+pub struct SampleQuality(pub u16);
+
+impl SampleQuality {
+    pub const OK: Self = Self(0);
+    /// counter encoder pada firmware wraparound (nilai di atas `wrap_limit`)
+    pub const ENCODER_WRAP: Self = Self(1 << 0);
+    /// pulse encoder lebih sedikit dari putaran yang dihitung Z index
+    pub const MISSED_PULSE: Self = Self(1 << 1);
+    /// pulse encoder lebih banyak dari putaran yang dihitung Z index (noise atau Z terlewat)
+    pub const Z_MISMATCH: Self = Self(1 << 2);
+    /// jumlah putaran dikoreksi menggunakan Z index
+    pub const CORRECTED: Self = Self(1 << 3);
+    /// `period` atau `pulse_enc_max` bernilai nol
+    pub const INVALID: Self = Self(1 << 4);
+    /// hasil perhitungan NaN / infinite, diganti dengan nilai sebelumnya
+    pub const SUBSTITUTED: Self = Self(1 << 5);
+    /// hasil perhitungan negatif, diganti dengan nilai sebelumnya
+    pub const CLIPPED: Self = Self(1 << 6);
+    /// spike yang terdeteksi oleh [`HampelConfig`]
+    pub const OUTLIER: Self = Self(1 << 7);
+    /// nilai sensor (suhu, load cell) tidak valid atau di luar batas
+    pub const SENSOR_FAULT: Self = Self(1 << 8);
+
+    const NAMES: [(Self, &'static str); 9] = [
+        (Self::ENCODER_WRAP, "ENCODER_WRAP"),
+        (Self::MISSED_PULSE, "MISSED_PULSE"),
+        (Self::Z_MISMATCH, "Z_MISMATCH"),
+        (Self::CORRECTED, "CORRECTED"),
+        (Self::INVALID, "INVALID"),
+        (Self::SUBSTITUTED, "SUBSTITUTED"),
+        (Self::CLIPPED, "CLIPPED"),
+        (Self::OUTLIER, "OUTLIER"),
+        (Self::SENSOR_FAULT, "SENSOR_FAULT"),
+    ];
+
+    #[inline]
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    #[inline]
+    pub const fn is_ok(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    #[inline]
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    #[inline]
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    #[inline]
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+
+    /// ## same as `if_not_normal`, but flag the sample when `value` is substituted
+    #[inline]
+    pub fn substitute<T: Numeric>(&mut self, value: T, def: T) -> T {
+        let v = value.to_float();
+        if v.is_nan() || v.is_infinite() {
+            self.insert(Self::SUBSTITUTED);
+            return def;
+        }
+        value
+    }
+
+    /// ## same as `if_negative_normal`, but flag the sample when `value` is substituted
+    #[inline]
+    pub fn clip<T: Numeric>(&mut self, value: T, def: T) -> T {
+        if value.to_float().is_sign_negative() {
+            self.insert(Self::CLIPPED);
+            return def;
+        }
+        self.substitute(value, def)
+    }
+}
+
+impl std::ops::BitOr for SampleQuality {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for SampleQuality {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.insert(rhs)
+    }
+}
+
+impl std::fmt::Display for SampleQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_ok() {
+            return f.write_str("OK");
+        }
+        let mut first = true;
+        for (flag, name) in Self::NAMES {
+            if self.contains(flag) {
+                if !first {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// # Encoder configuration
+/// validasi pulse encoder terhadap Z index (satu pulse setiap satu putaran)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct EncoderConfig {
+    /// gunakan `pulse_enc_z` untuk validasi dan koreksi putaran,
+    /// default `false` karena firmware tanpa sensor Z index selalu mengirim `pulse_enc_z = 0`
+    pub use_z_index: bool,
+    /// `pulse_enc` di atas nilai ini dianggap counter firmware wraparound
+    pub wrap_limit: u32,
+    /// toleransi selisih putaran encoder dan Z index (putaran)
+    pub tolerance: Float,
+}
+
+impl Default for EncoderConfig {
+    fn default() -> Self {
+        Self {
+            use_z_index: false,
+            wrap_limit: 2_000_000,
+            tolerance: 0.05,
+        }
+    }
+}
+
+impl EncoderConfig {
+    /// ## roller revolutions in one period of the serial data
+    /// dalam satu period, `z` pulse Z index berarti putaran sebenarnya di antara `z - 1` dan `z + 1`.
+    /// jika putaran dari encoder di bawah batas tersebut ada pulse yang terlewat dan putaran
+    /// dikoreksi menjadi `z`, jika di atas batas sample hanya ditandai [`SampleQuality::Z_MISMATCH`].
+    /// ```
+    /// use dyno_core::{EncoderConfig, SampleQuality, SerialData};
+    /// let serial = SerialData { period: 50, pulse_enc_max: 360, pulse_enc: 180, pulse_enc_z: 3, ..Default::default() };
+    /// let config = EncoderConfig { use_z_index: true, ..Default::default() };
+    /// let (revs, quality) = config.revolutions(&serial);
+    /// assert_eq!(revs, 3.0);
+    /// assert_eq!(quality, SampleQuality::MISSED_PULSE | SampleQuality::CORRECTED);
+    /// ```
+    pub fn revolutions(&self, serial: &SerialData) -> (Float, SampleQuality) {
+        let mut quality = SampleQuality::OK;
+        if serial.period == 0 || serial.pulse_enc_max == 0 {
+            quality |= SampleQuality::INVALID;
+        }
+        let z = serial.pulse_enc_z as Float;
+        if serial.pulse_enc > self.wrap_limit {
+            quality |= SampleQuality::ENCODER_WRAP;
+            if !self.use_z_index {
+                return (0.0, quality);
+            }
+            return (z, quality | SampleQuality::CORRECTED);
+        }
+        if serial.pulse_enc_max == 0 {
+            return (0.0, quality);
+        }
+
+        let revs = serial.pulse_enc as Float / serial.pulse_enc_max as Float;
+        if !self.use_z_index {
+            return (revs, quality);
+        }
+        if revs < z - 1.0 - self.tolerance {
+            return (
+                z,
+                quality | SampleQuality::MISSED_PULSE | SampleQuality::CORRECTED,
+            );
+        }
+        if revs > z + 1.0 + self.tolerance {
+            quality |= SampleQuality::Z_MISMATCH;
+        }
+        (revs, quality)
+    }
+}
+
+/// # Quality configuration
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct QualityConfig {
+    /// batas bawah suhu yang valid dari sensor
+    pub temp_min: Celcius,
+    /// batas atas suhu yang valid dari sensor
+    pub temp_max: Celcius,
+    pub outlier: HampelConfig,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            temp_min: Celcius(-40.0),
+            temp_max: Celcius(1200.0),
+            outlier: HampelConfig::default(),
+        }
+    }
+}
+
+impl QualityConfig {
+    /// ## check the sensor temperature, return the temperature to use
+    #[inline]
+    pub fn check_temp(&self, temp: Celcius, def: Celcius, quality: &mut SampleQuality) -> Celcius {
+        if !(temp.is_finite() && temp >= self.temp_min && temp <= self.temp_max) {
+            quality.insert(SampleQuality::SENSOR_FAULT);
+        }
+        quality.substitute(temp, def)
+    }
+}
+
+/// # Hampel outlier detector
+/// sample ditandai [`SampleQuality::OUTLIER`] jika `|x - median| > threshold * 1.4826 * MAD`
+/// dalam window `2 * half_window + 1` sample di sekitarnya.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct HampelConfig {
+    /// tandai outlier secara realtime saat data masuk
+    pub enabled: bool,
+    pub half_window: usize,
+    /// batas dalam satuan standar deviasi (estimasi dari MAD)
+    pub threshold: Float,
+}
+
+impl Default for HampelConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            half_window: 3,
+            threshold: 3.0,
+        }
+    }
+}
+
+impl HampelConfig {
+    const MAD_SCALE: Float = 1.4826;
+    /// selisih relatif minimum, window konstan (MAD = 0) tidak menandai noise floating point
+    const MIN_DEVIATION: Float = 1e-9;
+
+    /// ## check if `values[index]` is an outlier within its window
+    pub fn is_outlier(&self, values: &[Float], index: usize) -> bool {
+        let Some(value) = values.get(index).copied() else {
+            return false;
+        };
+        let start = index.saturating_sub(self.half_window);
+        let end = (index + self.half_window + 1).min(values.len());
+        let mut window = values[start..end]
+            .iter()
+            .copied()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        if window.len() < 3 || !value.is_finite() {
+            return false;
+        }
+        let center = median(&mut window);
+        window.iter_mut().for_each(|v| *v = (*v - center).abs());
+        let mad = median(&mut window);
+        let deviation = (value - center).abs();
+        deviation > self.threshold * Self::MAD_SCALE * mad
+            && deviation > Self::MIN_DEVIATION * center.abs().max(1.0)
+    }
+
+    /// ## flag every outlier of `values`
+    pub fn detect(&self, values: &[Float]) -> Vec<bool> {
+        (0..values.len())
+            .map(|idx| self.is_outlier(values, idx))
+            .collect()
+    }
+}
+
+/// median dari `values`, urutan `values` berubah
+pub(crate) fn median(values: &mut [Float]) -> Float {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let (len, mid) = (values.len(), values.len() / 2);
+    let cmp = |a: &Float, b: &Float| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal);
+    let (lower, upper, _) = values.select_nth_unstable_by(mid, cmp);
+    let upper = *upper;
+    if len % 2 == 1 {
+        return upper;
+    }
+    let lower = lower.iter().copied().max_by(cmp).unwrap_or(upper);
+    (lower + upper) * 0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERIAL: SerialData = SerialData {
+        period: 200,
+        pulse_enc_max: 360,
+        pulse_enc: 4200,
+        pulse_enc_z: 11,
+        pulse_rpm: 0,
+        temperature: 0.0,
+    };
+
+    const Z_INDEX: EncoderConfig = EncoderConfig {
+        use_z_index: true,
+        wrap_limit: 2_000_000,
+        tolerance: 0.05,
+    };
+
+    #[test]
+    fn test_revolutions_valid() {
+        let config = Z_INDEX;
+        let (revs, quality) = config.revolutions(&SERIAL);
+        assert_eq!(revs, 4200.0 / 360.0);
+        assert!(quality.is_ok());
+
+        // Z index belum lewat satu putaran
+        let serial = SerialData {
+            pulse_enc: 300,
+            pulse_enc_z: 0,
+            ..SERIAL
+        };
+        assert!(config.revolutions(&serial).1.is_ok());
+
+        // firmware tanpa sensor Z index
+        let serial = SerialData {
+            pulse_enc_z: 0,
+            ..SERIAL
+        };
+        assert!(EncoderConfig::default().revolutions(&serial).1.is_ok());
+    }
+
+    #[test]
+    fn test_revolutions_wrap() {
+        let serial = SerialData {
+            pulse_enc: u32::MAX - 10,
+            ..SERIAL
+        };
+        let (revs, quality) = Z_INDEX.revolutions(&serial);
+        assert_eq!(revs, 11.0);
+        assert!(quality.contains(SampleQuality::ENCODER_WRAP | SampleQuality::CORRECTED));
+
+        let config = EncoderConfig {
+            use_z_index: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            config.revolutions(&serial),
+            (0.0, SampleQuality::ENCODER_WRAP)
+        );
+    }
+
+    #[test]
+    fn test_revolutions_mismatch() {
+        let serial = SerialData {
+            pulse_enc: 4200,
+            pulse_enc_z: 9,
+            ..SERIAL
+        };
+        let (revs, quality) = Z_INDEX.revolutions(&serial);
+        assert_eq!(revs, 4200.0 / 360.0);
+        assert_eq!(quality, SampleQuality::Z_MISMATCH);
+        assert_eq!(quality.to_string(), "Z_MISMATCH");
+        assert_eq!(
+            (SampleQuality::MISSED_PULSE | SampleQuality::CORRECTED).to_string(),
+            "MISSED_PULSE | CORRECTED"
+        );
+    }
+
+    #[test]
+    fn test_substitute_and_clip() {
+        let mut quality = SampleQuality::OK;
+        assert_eq!(
+            quality.substitute(NewtonMeter(1.0), NewtonMeter(2.0)),
+            NewtonMeter(1.0)
+        );
+        assert!(quality.is_ok());
+        assert_eq!(
+            quality.clip(NewtonMeter(-1.0), NewtonMeter(2.0)),
+            NewtonMeter(2.0)
+        );
+        assert_eq!(quality, SampleQuality::CLIPPED);
+        assert_eq!(
+            quality.substitute(HorsePower(Float::NAN), HorsePower(3.0)),
+            HorsePower(3.0)
+        );
+        assert_eq!(quality, SampleQuality::CLIPPED | SampleQuality::SUBSTITUTED);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), 2.5);
+        assert_eq!(median(&mut []), 0.0);
+    }
+
+    #[test]
+    fn test_hampel() {
+        let mut values = (0..30).map(|i| i as Float * 10.0).collect::<Vec<_>>();
+        values[15] = 1000.0;
+        values[22] = -50.0;
+        let outliers = HampelConfig::default().detect(&values);
+        let flagged = outliers
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, o)| o.then_some(idx))
+            .collect::<Vec<_>>();
+        assert_eq!(flagged, [15, 22]);
+
+        // step tidak dianggap outlier
+        let step = (0..20)
+            .map(|i| crate::ternary!((i < 10)?(0.0): (500.0)))
+            .collect::<Vec<Float>>();
+        assert!(HampelConfig::default().detect(&step).iter().all(|o| !o));
+    }
+}
@@ -0,0 +1,244 @@
+//! # Resample
+//! interpolasi [`BufferData`] ke timestamp dengan interval tetap (mis. 50 Hz),
+//! sehingga dua rekaman dengan `period` yang berubah-ubah dapat dibandingkan per titik.
+use crate::{Buffer, DynoErr, DynoResult, Float, Numeric};
+
+use super::data_buffer::BufferData;
+
+/// # Interpolation
+/// metode interpolasi setiap channel saat resampling
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    derive_more::Display,
+)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    /// cubic Hermite, tangent dari rata-rata gradien kedua sisi sample
+    Cubic,
+}
+
+impl Interpolation {
+    /// ## interpolated value at time `at` between sample `index` and `index + 1`
+    /// `time` dan `values` harus memiliki panjang yang sama, `time` monoton naik
+    pub fn interpolate(self, time: &[Float], values: &[Float], index: usize, at: Float) -> Float {
+        let (Some(t0), Some(t1)) = (time.get(index), time.get(index + 1)) else {
+            return values.get(index).copied().unwrap_or_default();
+        };
+        let (y0, y1) = (values[index], values[index + 1]);
+        let h = t1 - t0;
+        if h <= 0.0 {
+            return y0;
+        }
+        let s = (at - t0) / h;
+        match self {
+            Self::Linear => y0 + (y1 - y0) * s,
+            Self::Cubic => {
+                let m0 = Self::tangent(time, values, index);
+                let m1 = Self::tangent(time, values, index + 1);
+                let (s2, s3) = (s * s, s * s * s);
+                (2.0 * s3 - 3.0 * s2 + 1.0) * y0
+                    + (s3 - 2.0 * s2 + s) * h * m0
+                    + (-2.0 * s3 + 3.0 * s2) * y1
+                    + (s3 - s2) * h * m1
+            }
+        }
+    }
+
+    /// gradien pada sample `index`, satu sisi pada ujung data
+    fn tangent(time: &[Float], values: &[Float], index: usize) -> Float {
+        let slope = |a: usize, b: usize| match time[b] - time[a] {
+            dt if dt > 0.0 => (values[b] - values[a]) / dt,
+            _ => 0.0,
+        };
+        let last = time.len() - 1;
+        match index {
+            0 => slope(0, 1),
+            i if i == last => slope(last - 1, last),
+            i => (slope(i - 1, i) + slope(i, i + 1)) * 0.5,
+        }
+    }
+}
+
+/// # Resample metadata
+/// disimpan pada `BufferData::resample_info` hasil [`BufferData::resample`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ResampleInfo {
+    /// sample rate hasil resampling (Hz)
+    pub rate_hz: Float,
+    /// sample rate rata-rata rekaman asli (Hz)
+    pub original_rate_hz: Float,
+    /// jumlah sample rekaman asli
+    pub original_len: usize,
+    pub interpolation: Interpolation,
+}
+
+impl BufferData {
+    /// jumlah sample maksimal hasil [`BufferData::resample`]
+    pub const MAX_RESAMPLE_LEN: usize = 4_000_000;
+
+    /// ## new [`BufferData`] resampled at fixed `rate_hz`, start from the first timestamp
+    /// setiap channel diinterpolasi dengan `interpolation`, kualitas sample adalah gabungan
+    /// kualitas kedua sample asli di sekitarnya. raw capture tidak ikut di-resample.
+    /// return error if `rate_hz` is not finite and positive, or the result exceeds
+    /// [`BufferData::MAX_RESAMPLE_LEN`] samples
+    /// ```
+    /// use dyno_core::*;
+    /// let data = [0, 30, 40, 100]
+    ///     .map(|ms| Data {
+    ///         speed: KilometresPerHour(ms as Float),
+    ///         time_stamp: chrono::NaiveDateTime::from_timestamp_millis(ms).unwrap(),
+    ///         ..Default::default()
+    ///     });
+    /// let mut buffer = BufferData::new();
+    /// buffer.extend_data(data);
+    /// let uniform = buffer.resample(50.0, Interpolation::Linear).unwrap();
+    /// assert_eq!(*uniform.time_stamp, [0, 20, 40, 60, 80, 100]);
+    /// assert_eq!(uniform.speed[3], KilometresPerHour(60.0));
+    /// assert_eq!(uniform.resample_info.unwrap().original_rate_hz, 30.0);
+    /// ```
+    pub fn resample(&self, rate_hz: Float, interpolation: Interpolation) -> DynoResult<Self> {
+        if !rate_hz.is_finite() || rate_hz <= 0.0 {
+            return Err(DynoErr::validation_error(format!(
+                "Invalid resample rate: {rate_hz} Hz"
+            )));
+        }
+        let len = self.len.min(self.time_stamp.len());
+        let (start, end) = match self.time_stamp.get(..len) {
+            Some([first, .., last]) => (*first, *last),
+            _ => {
+                return Err(DynoErr::validation_error(
+                    "Buffer needs at least 2 samples to resample",
+                ))
+            }
+        };
+        if end <= start || self.time_stamp[..len].windows(2).any(|w| w[1] < w[0]) {
+            return Err(DynoErr::validation_error(
+                "Buffer timestamp is not monotonically increasing",
+            ));
+        }
+        // waktu relatif terhadap sample pertama (ms), timestamp epoch tidak presisi pada f32
+        let time = self.time_stamp[..len]
+            .iter()
+            .map(|t| (t - start) as Float)
+            .collect::<Vec<_>>();
+        let duration = (end - start) as Float;
+
+        let step = 1000.0 / rate_hz;
+        let count = (duration / step + 1e-9).floor() + 1.0;
+        if !count.is_finite() || count > Self::MAX_RESAMPLE_LEN as Float {
+            return Err(DynoErr::validation_error(format!(
+                "Resample at {rate_hz} Hz exceeds {} samples",
+                Self::MAX_RESAMPLE_LEN
+            )));
+        }
+        let count = count as usize;
+        let mut index = 0;
+        let points = (0..count)
+            .map(|n| {
+                let at = n as Float * step;
+                while index + 2 < len && time[index + 1] <= at {
+                    index += 1;
+                }
+                (index, at)
+            })
+            .collect::<Vec<_>>();
+
+        let grid = ResampleGrid {
+            time: &time,
+            points: &points,
+            interpolation,
+        };
+        let quality = match self.quality.len() >= len {
+            true => points
+                .iter()
+                .map(
+                    |(idx, at)| match (*at <= time[*idx], *at >= time[idx + 1]) {
+                        (true, _) => self.quality[*idx],
+                        (_, true) => self.quality[idx + 1],
+                        _ => {
+                            let mut quality = self.quality[*idx];
+                            quality.insert(self.quality[idx + 1]);
+                            quality
+                        }
+                    },
+                )
+                .collect(),
+            false => Vec::new(),
+        };
+        let mut time_stamp = Buffer::default();
+        time_stamp.extend(points.iter().map(|(_, at)| start + at.round() as i64));
+
+        let mut resampled = Self {
+            speed: grid.column(&self.speed),
+            rpm_roda: grid.column(&self.rpm_roda),
+            rpm_engine: grid.column(&self.rpm_engine),
+            torque: grid.column(&self.torque),
+            horsepower: grid.column(&self.horsepower),
+            temp: grid.column(&self.temp),
+            time_stamp,
+            torque_corrected: grid.column(&self.torque_corrected),
+            horsepower_corrected: grid.column(&self.horsepower_corrected),
+            torque_smooth: grid.column(&self.torque_smooth),
+            horsepower_smooth: grid.column(&self.horsepower_smooth),
+            rpm_roda_smooth: grid.column(&self.rpm_roda_smooth),
+            rpm_engine_smooth: grid.column(&self.rpm_engine_smooth),
+            temp_2: grid.column(&self.temp_2),
+            load_cell: grid.column(&self.load_cell),
+            quality,
+            len: points.len(),
+            total_time: ((count - 1) as Float * step).round() as u64,
+            resample_info: Some(ResampleInfo {
+                rate_hz,
+                original_rate_hz: self
+                    .resample_info
+                    .map(|info| info.original_rate_hz)
+                    .unwrap_or((len - 1) as Float * 1000.0 / duration),
+                original_len: self
+                    .resample_info
+                    .map(|info| info.original_len)
+                    .unwrap_or(len),
+                interpolation,
+            }),
+            ..Default::default()
+        };
+        resampled.data = resampled.sample(resampled.len - 1);
+        Ok(resampled)
+    }
+}
+
+/// titik resample sebagai (index sample asli sebelumnya, waktu relatif ms)
+struct ResampleGrid<'a> {
+    time: &'a [Float],
+    points: &'a [(usize, Float)],
+    interpolation: Interpolation,
+}
+
+impl ResampleGrid<'_> {
+    /// interpolasi satu channel, kosong jika channel belum dihitung (panjang kurang dari sample)
+    fn column<T: Numeric>(&self, buffer: &Buffer<T>) -> Buffer<T> {
+        let mut resampled = Buffer::default();
+        let len = self.time.len();
+        if buffer.len() < len {
+            return resampled;
+        }
+        let values = buffer[..len]
+            .iter()
+            .map(|v| v.to_float())
+            .collect::<Vec<_>>();
+        resampled.extend(self.points.iter().map(|(idx, at)| {
+            T::from_float(
+                self.interpolation
+                    .interpolate(self.time, &values, *idx, *at),
+            )
+        }));
+        resampled
+    }
+}
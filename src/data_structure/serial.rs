@@ -0,0 +1,589 @@
+//! # Serial Wire Protocol
+//! framing for data yang dikirim firmware lewat serial port.
+//!
+//! layout satu frame sebelum di-encode:
+//! ```text
+//! [SYNC: u8][LEN: u16 LE][PAYLOAD: LEN bytes][CRC16: u16 LE]
+//! ```
+//! kemudian frame di-encode dengan COBS (Consistent Overhead Byte Stuffing)
+//! sehingga tidak ada byte `0x00` di dalamnya, dan diakhiri dengan [`FRAME_DELIM`].
+//! CRC16 (CCITT-FALSE) dihitung dari byte `SYNC` sampai akhir `PAYLOAD`.
+//!
+//! isi `PAYLOAD` adalah satu [`SerialMessage`]:
+//! ```text
+//! data      : [MSG_DATA][VERSION: u8][SerialData / SerialDataV2 bytes]
+//! handshake : [MSG_HANDSHAKE][MIN VERSION: u8][MAX VERSION: u8]
+//! ```
+use crate::{DynoErr, DynoResult};
+
+use super::{SerialData, SerialDataV2};
+
+/// byte pertama dari setiap frame (sebelum COBS)
+pub const FRAME_SYNC: u8 = 0xA5;
+/// byte pemisah antar frame setelah COBS encoding
+pub const FRAME_DELIM: u8 = 0x00;
+/// ukuran maksimal payload dalam satu frame
+pub const MAX_PAYLOAD_LEN: usize = 1024;
+
+/// tag payload untuk [`SerialMessage::Data`]
+pub const MSG_DATA: u8 = b'D';
+/// tag payload untuk [`SerialMessage::Handshake`]
+pub const MSG_HANDSHAKE: u8 = b'H';
+
+const FRAME_HEADER_LEN: usize = 3;
+const FRAME_CRC_LEN: usize = 2;
+const MAX_RAW_FRAME_LEN: usize = FRAME_HEADER_LEN + MAX_PAYLOAD_LEN + FRAME_CRC_LEN;
+const MAX_ENCODED_FRAME_LEN: usize = MAX_RAW_FRAME_LEN + (MAX_RAW_FRAME_LEN / 254) + 2;
+
+/// # CRC-16/CCITT-FALSE
+/// ```
+/// use dyno_core::crc16_ccitt;
+/// assert_eq!(crc16_ccitt(b"123456789"), 0x29B1);
+/// ```
+pub fn crc16_ccitt(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0xFFFF_u16, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+fn cobs_encode(bytes: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len() + (bytes.len() / 254) + 2);
+    let mut code_idx = 0;
+    let mut code = 1u8;
+    out.push(0);
+    for &byte in bytes {
+        if byte == 0 {
+            out[code_idx] = code;
+            code_idx = out.len();
+            out.push(0);
+            code = 1;
+            continue;
+        }
+        out.push(byte);
+        code += 1;
+        if code == 0xFF {
+            out[code_idx] = code;
+            code_idx = out.len();
+            out.push(0);
+            code = 1;
+        }
+    }
+    out[code_idx] = code;
+    out
+}
+
+fn cobs_decode(bytes: &[u8]) -> DynoResult<Vec<u8>> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let code = bytes[idx] as usize;
+        if code == 0 {
+            return Err(DynoErr::serial_port_error(
+                "Framing Error: unexpected zero byte in COBS frame",
+            ));
+        }
+        let end = idx + code;
+        if end > bytes.len() {
+            return Err(DynoErr::serial_port_error(
+                "Framing Error: COBS block exceeds frame length",
+            ));
+        }
+        out.extend_from_slice(&bytes[idx + 1..end]);
+        if code < 0xFF && end < bytes.len() {
+            out.push(0);
+        }
+        idx = end;
+    }
+    Ok(out)
+}
+
+/// ## encode payload into a complete frame, ready to be written into serial port
+/// panic if `payload` is longer than [`MAX_PAYLOAD_LEN`]
+pub fn encode_frame(payload: &[u8]) -> Vec<u8> {
+    assert!(
+        payload.len() <= MAX_PAYLOAD_LEN,
+        "payload should not be longer than {MAX_PAYLOAD_LEN} bytes"
+    );
+    let mut raw = Vec::with_capacity(FRAME_HEADER_LEN + payload.len() + FRAME_CRC_LEN);
+    raw.push(FRAME_SYNC);
+    raw.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+    raw.extend_from_slice(payload);
+    let crc = crc16_ccitt(&raw);
+    raw.extend_from_slice(&crc.to_le_bytes());
+
+    let mut frame = cobs_encode(&raw);
+    frame.push(FRAME_DELIM);
+    frame
+}
+
+/// ## decode one frame (without the trailing [`FRAME_DELIM`]) into its payload
+pub fn decode_frame(frame: &[u8]) -> DynoResult<Vec<u8>> {
+    let raw = cobs_decode(frame)?;
+    if raw.len() < FRAME_HEADER_LEN + FRAME_CRC_LEN {
+        return Err(DynoErr::serial_port_error(format!(
+            "Framing Error: frame too short ({} bytes)",
+            raw.len()
+        )));
+    }
+    if raw[0] != FRAME_SYNC {
+        return Err(DynoErr::serial_port_error(format!(
+            "Framing Error: invalid sync byte `{:#04X}`",
+            raw[0]
+        )));
+    }
+    let len = u16::from_le_bytes([raw[1], raw[2]]) as usize;
+    if raw.len() != FRAME_HEADER_LEN + len + FRAME_CRC_LEN {
+        return Err(DynoErr::serial_port_error(format!(
+            "Framing Error: length mismatch (header: {len}, actual: {})",
+            raw.len() - FRAME_HEADER_LEN - FRAME_CRC_LEN
+        )));
+    }
+    let (body, crc) = raw.split_at(FRAME_HEADER_LEN + len);
+    let expected = u16::from_le_bytes([crc[0], crc[1]]);
+    let actual = crc16_ccitt(body);
+    if expected != actual {
+        return Err(DynoErr::serial_port_error(format!(
+            "CRC Error: expected `{expected:#06X}`, got `{actual:#06X}`"
+        )));
+    }
+    Ok(body[FRAME_HEADER_LEN..].to_vec())
+}
+
+#[repr(u8)]
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub enum ProtocolVersion {
+    #[default]
+    V1 = 1,
+    V2 = 2,
+}
+
+impl ProtocolVersion {
+    pub const MIN: Self = Self::V1;
+    pub const LATEST: Self = Self::V2;
+
+    /// ukuran body data untuk versi ini
+    #[inline]
+    pub const fn data_size(self) -> usize {
+        match self {
+            Self::V1 => SerialData::SIZE,
+            Self::V2 => SerialDataV2::SIZE,
+        }
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    #[inline(always)]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", *self as u8)
+    }
+}
+
+impl TryFrom<u8> for ProtocolVersion {
+    type Error = DynoErr;
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        match val {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            v => Err(DynoErr::serial_port_error(format!(
+                "Protocol Error: unsupported protocol version `{v}`"
+            ))),
+        }
+    }
+}
+
+/// # Handshake message
+/// dikirim oleh board saat terhubung untuk memberitahu range versi protocol
+/// yang didukung, host membalas dengan [`Handshake::host`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handshake {
+    pub min: ProtocolVersion,
+    pub max: ProtocolVersion,
+}
+
+impl Default for Handshake {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+impl Handshake {
+    /// range versi protocol yang didukung oleh library ini
+    pub const fn host() -> Self {
+        Self {
+            min: ProtocolVersion::MIN,
+            max: ProtocolVersion::LATEST,
+        }
+    }
+
+    /// ## negotiate the highest protocol version supported by both side
+    /// ```
+    /// use dyno_core::{Handshake, ProtocolVersion};
+    /// let board = Handshake { min: ProtocolVersion::V1, max: ProtocolVersion::V1 };
+    /// assert_eq!(Handshake::host().negotiate(&board), Some(ProtocolVersion::V1));
+    /// ```
+    pub fn negotiate(&self, other: &Self) -> Option<ProtocolVersion> {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max);
+        crate::ternary!((min <= max)?(Some(max)): (None))
+    }
+
+    #[inline]
+    pub fn to_frame(&self) -> Vec<u8> {
+        SerialMessage::Handshake(*self).to_frame()
+    }
+}
+
+/// # Versioned data payload from the board
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum SerialPayload {
+    V1(SerialData),
+    V2(SerialDataV2),
+}
+
+impl Default for SerialPayload {
+    fn default() -> Self {
+        Self::V1(SerialData::default())
+    }
+}
+
+impl From<SerialData> for SerialPayload {
+    fn from(data: SerialData) -> Self {
+        Self::V1(data)
+    }
+}
+
+impl From<SerialDataV2> for SerialPayload {
+    fn from(data: SerialDataV2) -> Self {
+        Self::V2(data)
+    }
+}
+
+impl SerialPayload {
+    #[inline]
+    pub const fn version(&self) -> ProtocolVersion {
+        match self {
+            Self::V1(_) => ProtocolVersion::V1,
+            Self::V2(_) => ProtocolVersion::V2,
+        }
+    }
+
+    /// channel yang tersedia pada semua versi protocol
+    #[inline]
+    pub const fn base(&self) -> SerialData {
+        match self {
+            Self::V1(data) => *data,
+            Self::V2(data) => data.base,
+        }
+    }
+
+    pub fn from_bytes(version: ProtocolVersion, bytes: &'_ [u8]) -> Option<Self> {
+        match version {
+            ProtocolVersion::V1 => SerialData::from_bytes(bytes).map(Self::V1),
+            ProtocolVersion::V2 => SerialDataV2::from_bytes(bytes).map(Self::V2),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::V1(data) => data.to_bytes().to_vec(),
+            Self::V2(data) => data.to_bytes().to_vec(),
+        }
+    }
+
+    #[inline]
+    pub fn to_frame(&self) -> Vec<u8> {
+        SerialMessage::Data(*self).to_frame()
+    }
+}
+
+/// # Message carried inside one frame
+#[derive(Debug, Clone, Copy)]
+pub enum SerialMessage {
+    Handshake(Handshake),
+    Data(SerialPayload),
+}
+
+impl SerialMessage {
+    pub fn from_payload(payload: &'_ [u8]) -> DynoResult<Self> {
+        match payload {
+            [MSG_HANDSHAKE, min, max] => Ok(Self::Handshake(Handshake {
+                min: ProtocolVersion::try_from(*min)?,
+                max: ProtocolVersion::try_from(*max)?,
+            })),
+            [MSG_DATA, version, body @ ..] => {
+                let version = ProtocolVersion::try_from(*version)?;
+                SerialPayload::from_bytes(version, body)
+                    .map(Self::Data)
+                    .ok_or_else(|| {
+                        DynoErr::serial_port_error(format!(
+                            "Framing Error: invalid {version} payload size (expected: {}, got: {})",
+                            version.data_size(),
+                            body.len()
+                        ))
+                    })
+            }
+            _ => Err(DynoErr::serial_port_error(format!(
+                "Protocol Error: unknown message ({} bytes)",
+                payload.len()
+            ))),
+        }
+    }
+
+    pub fn to_payload(&self) -> Vec<u8> {
+        match self {
+            Self::Handshake(Handshake { min, max }) => vec![MSG_HANDSHAKE, *min as u8, *max as u8],
+            Self::Data(data) => {
+                let mut payload = vec![MSG_DATA, data.version() as u8];
+                payload.extend_from_slice(&data.to_bytes());
+                payload
+            }
+        }
+    }
+
+    #[inline]
+    pub fn to_frame(&self) -> Vec<u8> {
+        encode_frame(&self.to_payload())
+    }
+}
+
+/// # Incremental frame decoder
+/// menerima potongan bytes (chunk) dengan ukuran sembarang dari serial port,
+/// dan mengembalikan [`SerialPayload`] setiap kali satu frame lengkap diterima.
+/// bytes sampah akan dilaporkan sebagai error, lalu decoder melakukan
+/// sinkronisasi ulang pada [`FRAME_DELIM`] berikutnya.
+/// setelah menerima [`Handshake`] dari board, decoder hanya menerima data
+/// dengan versi protocol hasil negosiasi.
+///
+/// ```
+/// use dyno_core::{SerialData, SerialFrameDecoder};
+/// let data = SerialData { period: 200, pulse_enc: 10, ..Default::default() };
+/// let frame = data.to_frame();
+///
+/// let mut decoder = SerialFrameDecoder::new();
+/// decoder.feed(b"garbage\x00");
+/// decoder.feed(&frame);
+/// assert!(decoder.next_data().unwrap().is_err());
+/// let decoded = decoder.next_data().unwrap().unwrap();
+/// assert_eq!(decoded.base().pulse_enc, 10);
+/// assert!(decoder.next_data().is_none());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SerialFrameDecoder {
+    buffer: Vec<u8>,
+    discarding: bool,
+    version: Option<ProtocolVersion>,
+}
+
+impl SerialFrameDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub fn feed(&mut self, chunk: impl AsRef<[u8]>) {
+        self.buffer.extend_from_slice(chunk.as_ref());
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.discarding = false;
+        self.version = None;
+    }
+
+    /// versi protocol hasil negosiasi dengan board, `None` jika belum ada handshake
+    #[inline]
+    pub const fn version(&self) -> Option<ProtocolVersion> {
+        self.version
+    }
+
+    /// ## get the next payload from received bytes
+    /// return `None` if there is no complete frame yet
+    pub fn next_payload(&mut self) -> Option<DynoResult<Vec<u8>>> {
+        loop {
+            let Some(pos) = self.buffer.iter().position(|&b| b == FRAME_DELIM) else {
+                if self.discarding {
+                    self.buffer.clear();
+                } else if self.buffer.len() > MAX_ENCODED_FRAME_LEN {
+                    self.buffer.clear();
+                    self.discarding = true;
+                    return Some(Err(DynoErr::serial_port_error(
+                        "Framing Error: frame exceeds maximum length, discarding until next delimiter",
+                    )));
+                }
+                return None;
+            };
+            let frame = self.buffer.drain(..=pos).collect::<Vec<_>>();
+            if self.discarding {
+                self.discarding = false;
+                continue;
+            }
+            if frame.len() == 1 {
+                // empty frame, consecutive delimiter
+                continue;
+            }
+            return Some(decode_frame(&frame[..frame.len() - 1]));
+        }
+    }
+
+    /// ## get the next [`SerialMessage`] from received bytes
+    /// return `None` if there is no complete frame yet
+    pub fn next_message(&mut self) -> Option<DynoResult<SerialMessage>> {
+        self.next_payload()
+            .map(|payload| payload.and_then(|payload| SerialMessage::from_payload(&payload)))
+    }
+
+    /// ## get the next [`SerialPayload`] from received bytes
+    /// handshake message is consumed to negotiate the protocol version,
+    /// return `None` if there is no complete frame yet
+    pub fn next_data(&mut self) -> Option<DynoResult<SerialPayload>> {
+        loop {
+            match self.next_message()? {
+                Ok(SerialMessage::Handshake(board)) => {
+                    self.version = Handshake::host().negotiate(&board);
+                    if self.version.is_none() {
+                        return Some(Err(DynoErr::serial_port_error(format!(
+                            "Protocol Error: board protocol ({} - {}) is not supported",
+                            board.min, board.max
+                        ))));
+                    }
+                }
+                Ok(SerialMessage::Data(data)) => match self.version {
+                    Some(version) if version != data.version() => {
+                        return Some(Err(DynoErr::serial_port_error(format!(
+                            "Protocol Error: expected {version} payload, got {}",
+                            data.version()
+                        ))))
+                    }
+                    _ => return Some(Ok(data)),
+                },
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: SerialData = SerialData {
+        period: 200,
+        pulse_enc_max: 360,
+        pulse_enc: 0x0A0A_000A,
+        pulse_enc_z: 0,
+        pulse_rpm: 69,
+        temperature: 42.0,
+    };
+
+    #[test]
+    fn test_cobs_roundtrip() {
+        let inputs: [&[u8]; 4] = [&[], &[0], &[0, 0, 1, 0], &[0x11; 600]];
+        for input in inputs {
+            let encoded = cobs_encode(input);
+            assert!(!encoded.contains(&0));
+            assert_eq!(cobs_decode(&encoded).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_serial_data_le_bytes() {
+        let bytes = DATA.to_bytes();
+        assert_eq!(&bytes[8..12], &[0x0A, 0x00, 0x0A, 0x0A]);
+        assert_eq!(&bytes[20..], &42f32.to_le_bytes());
+
+        // misaligned source buffer
+        let mut shifted = vec![0u8; SerialData::SIZE + 1];
+        shifted[1..].copy_from_slice(&bytes);
+        let data = SerialData::from_bytes(&shifted[1..]).unwrap();
+        assert_eq!(data.pulse_enc, DATA.pulse_enc);
+        assert_eq!(data.temperature, DATA.temperature);
+        assert!(SerialData::from_bytes(&shifted).is_none());
+    }
+
+    #[test]
+    fn test_decode_chunked() {
+        let frame = DATA.to_frame();
+        let mut decoder = SerialFrameDecoder::new();
+        for chunk in frame.chunks(3) {
+            decoder.feed(chunk);
+        }
+        let data = decoder.next_data().unwrap().unwrap().base();
+        assert_eq!(data.pulse_enc, DATA.pulse_enc);
+        assert_eq!(data.temperature, DATA.temperature);
+        assert!(decoder.next_data().is_none());
+    }
+
+    #[test]
+    fn test_decode_versioned() {
+        let v2 = SerialDataV2 {
+            base: DATA,
+            temperature_2: 36.5,
+            load_cell: 12.0,
+        };
+        let mut decoder = SerialFrameDecoder::new();
+        decoder.feed(v2.to_frame());
+        assert!(matches!(
+            decoder.next_data(),
+            Some(Ok(SerialPayload::V2(SerialDataV2 { load_cell, .. }))) if load_cell == 12.0
+        ));
+
+        let board = Handshake {
+            min: ProtocolVersion::V1,
+            max: ProtocolVersion::V1,
+        };
+        decoder.feed(board.to_frame());
+        decoder.feed(v2.to_frame());
+        decoder.feed(DATA.to_frame());
+        assert!(decoder.next_data().unwrap().is_err());
+        assert_eq!(decoder.version(), Some(ProtocolVersion::V1));
+        assert!(matches!(
+            decoder.next_data(),
+            Some(Ok(SerialPayload::V1(_)))
+        ));
+    }
+
+    #[test]
+    fn test_decode_crc_error() {
+        let frame = DATA.to_frame();
+        let mut raw = cobs_decode(&frame[..frame.len() - 1]).unwrap();
+        raw[4] ^= 0x01;
+        let mut frame = cobs_encode(&raw);
+        frame.push(FRAME_DELIM);
+        let mut decoder = SerialFrameDecoder::new();
+        decoder.feed(&frame);
+        decoder.feed(DATA.to_frame());
+        let err = decoder.next_data().unwrap().unwrap_err();
+        assert!(err.is_serial_port_error());
+        assert!(err.desc.starts_with("CRC Error"));
+        assert!(decoder.next_data().unwrap().is_ok());
+    }
+
+    #[test]
+    fn test_decode_overflow_resync() {
+        let mut decoder = SerialFrameDecoder::new();
+        decoder.feed(vec![0x01; MAX_ENCODED_FRAME_LEN + 1]);
+        assert!(decoder.next_data().unwrap().is_err());
+        decoder.feed([0x01, 0x02, FRAME_DELIM]);
+        decoder.feed(DATA.to_frame());
+        assert!(decoder.next_data().unwrap().is_ok());
+        assert!(decoder.next_data().is_none());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::{convertions::prelude::*, DynoConfig, SerialPayload};
+
+use super::data_buffer::BufferData;
+
+/// # Session configuration
+/// threshold untuk mendeteksi awal dan akhir run secara otomatis
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// suhu minimal sebelum session siap (armed)
+    pub warmup_temp: Celcius,
+    /// lama minimal warmup (ms)
+    pub warmup_ms: u64,
+    /// rpm roda (roller) untuk mulai merekam
+    pub start_rpm: RotationPerMinute,
+    /// rpm roda (roller) untuk mengakhiri session saat coastdown
+    pub stop_rpm: RotationPerMinute,
+    /// persentase penurunan rpm dari puncak untuk mendeteksi throttle-off (0.0 - 1.0)
+    pub coastdown_drop: crate::Float,
+    /// jumlah sample berturut-turut di bawah threshold untuk masuk coastdown
+    pub coastdown_samples: usize,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            warmup_temp: Celcius(0.0),
+            warmup_ms: 0,
+            start_rpm: RotationPerMinute(300.0),
+            stop_rpm: RotationPerMinute(150.0),
+            coastdown_drop: 0.05,
+            coastdown_samples: 3,
+        }
+    }
+}
+
+#[derive(
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    derive_more::Display,
+)]
+pub enum SessionState {
+    #[default]
+    Idle,
+    Warmup,
+    Armed,
+    Recording,
+    Coastdown,
+    Finished,
+}
+
+impl SessionState {
+    #[inline]
+    pub const fn is_recording(self) -> bool {
+        matches!(self, Self::Recording | Self::Coastdown)
+    }
+}
+
+/// event yang dihasilkan saat state session berubah,
+/// `index` adalah posisi sample pada [`BufferData`]
+#[derive(serde::Deserialize, serde::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    WarmupStarted,
+    Armed,
+    RecordingStarted { index: usize },
+    CoastdownStarted { index: usize },
+    Finished { len: usize },
+}
+
+impl SessionEvent {
+    #[inline]
+    pub const fn state(&self) -> SessionState {
+        match self {
+            Self::WarmupStarted => SessionState::Warmup,
+            Self::Armed => SessionState::Armed,
+            Self::RecordingStarted { .. } => SessionState::Recording,
+            Self::CoastdownStarted { .. } => SessionState::Coastdown,
+            Self::Finished { .. } => SessionState::Finished,
+        }
+    }
+}
+
+/// # Dyno Session
+/// state machine di atas [`BufferData`] yang mendeteksi run secara otomatis:
+/// `Idle -> Warmup -> Armed -> Recording -> Coastdown -> Finished`.
+/// sample hanya disimpan ke buffer saat state `Recording` atau `Coastdown`.
+#[derive(Debug, Clone, Default)]
+pub struct DynoSession {
+    buffer: BufferData,
+    state: SessionState,
+    elapsed_ms: u64,
+    peak_rpm: RotationPerMinute,
+    below_peak: usize,
+    coastdown_index: Option<usize>,
+}
+
+impl DynoSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    pub const fn state(&self) -> SessionState {
+        self.state
+    }
+
+    #[inline]
+    pub const fn buffer(&self) -> &BufferData {
+        &self.buffer
+    }
+
+    #[inline]
+    pub fn into_buffer(self) -> BufferData {
+        self.buffer
+    }
+
+    /// index sample pertama dari fase coastdown, jika ada
+    #[inline]
+    pub const fn coastdown_index(&self) -> Option<usize> {
+        self.coastdown_index
+    }
+
+    /// ## reset the session into `Idle` and clear recorded buffer
+    pub fn reset(&mut self) {
+        let raw_capture = self.buffer.raw.enabled;
+        *self = Self::default();
+        self.buffer.raw.enabled = raw_capture;
+    }
+
+    /// ## retain raw serial payload of the recorded samples, see [`BufferData::recompute`]
+    #[inline]
+    pub fn set_raw_capture(&mut self, enabled: bool) {
+        self.buffer.raw.enabled = enabled;
+    }
+
+    /// ## force the session to finish, e.g. when the user stop the run manually
+    pub fn finish(&mut self) -> Option<SessionEvent> {
+        if self.state == SessionState::Finished {
+            return None;
+        }
+        self.state = SessionState::Finished;
+        Some(SessionEvent::Finished {
+            len: self.buffer.len(),
+        })
+    }
+
+    /// ## process one sample from serial port
+    /// return [`SessionEvent`] if the state changed on this sample
+    pub fn push_from_serial(
+        &mut self,
+        config: &'_ mut DynoConfig,
+        serial_data: impl Into<SerialPayload>,
+    ) -> Option<SessionEvent> {
+        if self.state == SessionState::Finished {
+            return None;
+        }
+        let serial_data = serial_data.into();
+        let session = config.session;
+        self.elapsed_ms += serial_data.base().period as u64;
+
+        if self.state.is_recording() {
+            self.buffer.push_from_serial(config, serial_data);
+        } else {
+            self.buffer.warmup_from_serial(config, serial_data);
+        }
+        let rpm = self.buffer.last().rpm_roda;
+
+        match self.state {
+            SessionState::Idle => {
+                self.state = SessionState::Warmup;
+                self.elapsed_ms = 0;
+                Some(SessionEvent::WarmupStarted)
+            }
+            SessionState::Warmup
+                if self.elapsed_ms >= session.warmup_ms
+                    && self.buffer.last().temp >= session.warmup_temp =>
+            {
+                self.state = SessionState::Armed;
+                Some(SessionEvent::Armed)
+            }
+            SessionState::Armed if rpm >= session.start_rpm => {
+                self.state = SessionState::Recording;
+                self.peak_rpm = rpm;
+                self.below_peak = 0;
+                // sample ini sample pertama rekaman, bukan warm-up
+                self.buffer.raw.warmup.pop();
+                self.buffer.record_serial(config, serial_data);
+                Some(SessionEvent::RecordingStarted {
+                    index: self.buffer.len() - 1,
+                })
+            }
+            SessionState::Recording => {
+                if rpm > self.peak_rpm {
+                    self.peak_rpm = rpm;
+                }
+                if rpm < self.peak_rpm * (1.0 - session.coastdown_drop) {
+                    self.below_peak += 1;
+                } else {
+                    self.below_peak = 0;
+                }
+                if self.below_peak < Ord::max(session.coastdown_samples, 1) {
+                    return None;
+                }
+                let index = self.buffer.len() - self.below_peak;
+                self.state = SessionState::Coastdown;
+                self.coastdown_index = Some(index);
+                Some(SessionEvent::CoastdownStarted { index })
+            }
+            SessionState::Coastdown if rpm < session.stop_rpm => self.finish(),
+            _ => None,
+        }
+    }
+}
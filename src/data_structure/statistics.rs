@@ -0,0 +1,222 @@
+//! # Descriptive Statistics
+//! statistik deskriptif [`Buffer`] atau sebagian index-nya, untuk laporan hasil pengujian
+//! (mis. persentil ke-95 temperature atau RMS ripple torque).
+//! statistik berbasis urutan (median, persentil, histogram) dihitung dari [`Distribution`].
+use std::ops::{Bound, Range, RangeBounds};
+
+use super::buffer::{Buffer, BufferStats};
+use crate::{Float, Numeric};
+
+/// # Distribution
+/// sample yang sudah diurutkan, nilai NaN dan infinite diabaikan
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Distribution {
+    sorted: Vec<Float>,
+}
+
+impl Distribution {
+    pub fn new(values: impl IntoIterator<Item = Float>) -> Self {
+        let mut sorted = values
+            .into_iter()
+            .filter(|v| v.is_finite())
+            .collect::<Vec<_>>();
+        sorted.sort_unstable_by(Float::total_cmp);
+        Self { sorted }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sorted.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sorted.is_empty()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[Float] {
+        &self.sorted
+    }
+
+    /// ## `p`-th percentile (0 - 100) with linear interpolation between samples
+    /// return `0.0` if there is no sample
+    pub fn percentile(&self, p: Float) -> Float {
+        let (Some(first), Some(last)) = (self.sorted.first(), self.sorted.last()) else {
+            return 0.0;
+        };
+        let rank = (p.clamp(0.0, 100.0) / 100.0) * (self.sorted.len() - 1) as Float;
+        if !rank.is_finite() {
+            return *first;
+        }
+        let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+        match (self.sorted.get(lower), self.sorted.get(upper)) {
+            (Some(a), Some(b)) => a + (b - a) * (rank - lower as Float),
+            _ => *last,
+        }
+    }
+
+    #[inline]
+    pub fn median(&self) -> Float {
+        self.percentile(50.0)
+    }
+
+    /// ## equal-width histogram with `bins` buckets from the minimum to the maximum sample
+    pub fn histogram(&self, bins: usize) -> Histogram {
+        let (Some(min), Some(max)) = (self.sorted.first(), self.sorted.last()) else {
+            return Histogram::default();
+        };
+        let mut histogram = Histogram {
+            min: *min,
+            max: *max,
+            counts: vec![0; Ord::max(bins, 1)],
+        };
+        self.sorted.iter().for_each(|v| histogram.insert(*v));
+        histogram
+    }
+}
+
+/// # Histogram
+/// jumlah sample pada setiap bucket dengan lebar yang sama antara `min` dan `max`,
+/// bucket terakhir termasuk `max`
+#[derive(Debug, Default, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct Histogram {
+    pub min: Float,
+    pub max: Float,
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    #[inline]
+    pub fn bin_width(&self) -> Float {
+        match self.counts.len() {
+            0 => 0.0,
+            n => (self.max - self.min) / n as Float,
+        }
+    }
+
+    /// ## value range of bucket `index`
+    #[inline]
+    pub fn bin_range(&self, index: usize) -> Range<Float> {
+        let width = self.bin_width();
+        let start = self.min + width * index as Float;
+        start..start + width
+    }
+
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    fn insert(&mut self, value: Float) {
+        let last = self.counts.len().saturating_sub(1);
+        let width = self.bin_width();
+        let index = match width > 0.0 {
+            true => (((value - self.min) / width) as usize).min(last),
+            false => 0,
+        };
+        if let Some(count) = self.counts.get_mut(index) {
+            *count += 1;
+        }
+    }
+}
+
+impl<T: Numeric> Buffer<T> {
+    /// sample pada `range`, dibatasi panjang buffer
+    fn range_slice(&self, range: impl RangeBounds<usize>) -> &[T] {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(s) => *s,
+            Bound::Excluded(s) => s.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(e) => e.saturating_add(1),
+            Bound::Excluded(e) => *e,
+            Bound::Unbounded => len,
+        }
+        .min(len);
+        &self[start.min(end)..end]
+    }
+
+    /// ## population standard deviation of the retained samples
+    #[inline]
+    pub fn std_value(&self) -> Float {
+        self.stats().std_dev()
+    }
+
+    /// ## root mean square of the retained samples
+    #[inline]
+    pub fn rms_value(&self) -> Float {
+        self.stats().rms()
+    }
+
+    /// ## median of the retained samples
+    #[inline]
+    pub fn median_value(&self) -> Float {
+        self.distribution(..).median()
+    }
+
+    /// ## `p`-th percentile (0 - 100) of the retained samples
+    #[inline]
+    pub fn percentile_value(&self, p: Float) -> Float {
+        self.distribution(..).percentile(p)
+    }
+
+    /// ## equal-width histogram of the retained samples
+    #[inline]
+    pub fn histogram(&self, bins: usize) -> Histogram {
+        self.distribution(..).histogram(bins)
+    }
+
+    /// ## count, sum, min, max, mean, variance and rms of samples in `range`
+    /// ```
+    /// use dyno_core::Buffer;
+    /// let mut buffer = Buffer::<f64>::default();
+    /// buffer.extend([1.0, 2.0, 3.0, 4.0, 100.0]);
+    /// let stats = buffer.stats_in(..4);
+    /// assert_eq!(stats.max, 4.0);
+    /// assert_eq!(stats.mean, 2.5);
+    /// assert_eq!(buffer.distribution(1..=3).median(), 3.0);
+    /// ```
+    #[inline]
+    pub fn stats_in(&self, range: impl RangeBounds<usize>) -> BufferStats<T> {
+        BufferStats::from_values(self.range_slice(range))
+    }
+
+    /// ## sorted samples in `range` for median, percentile and histogram
+    #[inline]
+    pub fn distribution(&self, range: impl RangeBounds<usize>) -> Distribution {
+        Distribution::new(self.range_slice(range).iter().map(|v| v.to_float()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let distribution = Distribution::new([4.0, 1.0, Float::NAN, 3.0, 2.0, 5.0]);
+        assert_eq!(distribution.len(), 5);
+        assert_eq!(distribution.median(), 3.0);
+        assert_eq!(distribution.percentile(0.0), 1.0);
+        assert_eq!(distribution.percentile(100.0), 5.0);
+        assert_eq!(distribution.percentile(25.0), 2.0);
+        assert_eq!(distribution.percentile(90.0), 4.6);
+        assert_eq!(Distribution::new([1.0, 2.0]).median(), 1.5);
+        assert_eq!(Distribution::default().percentile(50.0), 0.0);
+    }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Distribution::new((0..100).map(|x| x as Float)).histogram(4);
+        assert_eq!(histogram.counts, [25, 25, 25, 25]);
+        assert_eq!(histogram.bin_range(1), 24.75..49.5);
+        assert_eq!(histogram.total(), 100);
+
+        let constant = Distribution::new([2.0; 10]).histogram(3);
+        assert_eq!(constant.counts, [10, 0, 0]);
+        assert!(Distribution::default().histogram(3).counts.is_empty());
+    }
+}
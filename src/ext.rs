@@ -143,10 +143,14 @@ pub fn any_as_u8_slice<T: Sized>(p: &T) -> &[u8] {
         ::core::slice::from_raw_parts((p as *const T) as *const u8, ::core::mem::size_of::<T>())
     }
 }
+/// ## read `T` from raw bytes with host memory layout and endianness
+/// uses an unaligned read, so `b` does not need to be aligned for `T`.
+/// for data that comes from the wire, prefer an explicit decoder
+/// like [`crate::SerialData::from_bytes`]
 #[inline(always)]
 pub fn any_from_u8_slice<T: Sized>(b: &[u8]) -> T {
     assert!(b.len() == ::core::mem::size_of::<T>());
-    unsafe { ::core::ptr::read::<T>(b.as_ptr() as *const T) }
+    unsafe { ::core::ptr::read_unaligned::<T>(b.as_ptr() as *const T) }
 }
 
 pub trait BinSerializeDeserialize: serde::Serialize + serde::de::DeserializeOwned {
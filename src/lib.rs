@@ -12,11 +12,13 @@ mod ploting;
 #[cfg(feature = "use_log")]
 mod logger;
 
+pub mod analysis;
 pub mod convertions;
 pub mod data_structure;
 pub mod model;
 
 pub mod crypto;
+pub mod simulator;
 
 #[cfg(feature = "use_plot")]
 pub use ploting::*;
@@ -29,6 +31,7 @@ pub use error::*;
 pub use ext::*;
 pub use validator::*;
 
+pub use analysis::prelude::*;
 pub use convertions::prelude::*;
 pub use data_structure::prelude::*;
 pub use model::*;
@@ -230,4 +230,76 @@ macro_rules! impl_safe_math {
     };
 }
 
-pub(crate) use {impl_fuzzyeq, impl_numeric_float, impl_numeric_integer, impl_safe_math};
+/// serde enum dengan struct variant: internally tagged (`type = "..."`) pada format
+/// human-readable karena toml tidak mendukung struct variant externally tagged,
+/// dan externally tagged pada format biner karena bincode tidak mendukung `deserialize_any`
+macro_rules! impl_serde_tagged {
+    ($name:ident { $($variant:ident $({ $($field:ident: $ty:ty),* $(,)? })?),* $(,)? }) => {
+        const _: () = {
+            #[derive(serde::Serialize, serde::Deserialize)]
+            #[serde(tag = "type")]
+            enum Tagged {
+                $($variant $({ $($field: $ty),* })?),*
+            }
+
+            #[derive(serde::Serialize, serde::Deserialize)]
+            enum External {
+                $($variant $({ $($field: $ty),* })?),*
+            }
+
+            impl From<$name> for Tagged {
+                fn from(value: $name) -> Self {
+                    match value {
+                        $($name::$variant $({ $($field),* })? => Self::$variant $({ $($field),* })?),*
+                    }
+                }
+            }
+
+            impl From<Tagged> for $name {
+                fn from(value: Tagged) -> Self {
+                    match value {
+                        $(Tagged::$variant $({ $($field),* })? => Self::$variant $({ $($field),* })?),*
+                    }
+                }
+            }
+
+            impl From<$name> for External {
+                fn from(value: $name) -> Self {
+                    match value {
+                        $($name::$variant $({ $($field),* })? => Self::$variant $({ $($field),* })?),*
+                    }
+                }
+            }
+
+            impl From<External> for $name {
+                fn from(value: External) -> Self {
+                    match value {
+                        $(External::$variant $({ $($field),* })? => Self::$variant $({ $($field),* })?),*
+                    }
+                }
+            }
+
+            impl serde::Serialize for $name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    match serializer.is_human_readable() {
+                        true => Tagged::from(*self).serialize(serializer),
+                        false => External::from(*self).serialize(serializer),
+                    }
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    match deserializer.is_human_readable() {
+                        true => Tagged::deserialize(deserializer).map(Self::from),
+                        false => External::deserialize(deserializer).map(Self::from),
+                    }
+                }
+            }
+        };
+    };
+}
+
+pub(crate) use {
+    impl_fuzzyeq, impl_numeric_float, impl_numeric_integer, impl_safe_math, impl_serde_tagged,
+};
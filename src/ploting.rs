@@ -5,7 +5,7 @@ use plotly::{
     Configuration, Layout, Plot,
 };
 
-use crate::{dynotests::DynoTest, Buffer, BufferData, Numeric};
+use crate::{dynotests::DynoTest, Buffer, BufferData, Numeric, PowerCurve};
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct PlotColor {
@@ -198,6 +198,58 @@ impl DynoPlot {
         self
     }
 
+    pub fn create_power_curve(mut self, curve: &PowerCurve) -> Self {
+        let rpm = curve
+            .points
+            .iter()
+            .map(|p| p.rpm.to_f64())
+            .collect::<Vec<_>>();
+        let torque = curve
+            .points
+            .iter()
+            .map(|p| p.torque.to_f64())
+            .collect::<Vec<_>>();
+        let horsepower = curve
+            .points
+            .iter()
+            .map(|p| p.horsepower.to_f64())
+            .collect::<Vec<_>>();
+        self.add_trace(
+            plotly::Scatter::new(rpm.clone(), torque)
+                .mode(Mode::Lines)
+                .line(Line::new().shape(LineShape::Spline))
+                .name("Torque (Nm)")
+                .show_legend(true),
+        );
+        self.add_trace(
+            plotly::Scatter::new(rpm, horsepower)
+                .mode(Mode::Lines)
+                .line(Line::new().shape(LineShape::Spline))
+                .name("HorsePower (HP)")
+                .y_axis("y2")
+                .show_legend(true),
+        );
+
+        let layout = Layout::new()
+            .margin(Margin::new().top(40).bottom(20))
+            .font(Font::new().color(self.color.fg))
+            .plot_background_color(self.color.base)
+            .paper_background_color(self.color.base100)
+            .x_axis(Axis::new().title(Title::new(&format!("RPM ({})", curve.source))))
+            .y_axis(Axis::new().title(Title::new("Torque (Nm)")))
+            .y_axis2(
+                Axis::new()
+                    .title(Title::new("HorsePower (HP)"))
+                    .anchor("x")
+                    .overlaying("y")
+                    .side(AxisSide::Right),
+            )
+            .auto_size(true);
+
+        self.plot.set_layout(layout);
+        self
+    }
+
     #[cfg(feature = "use_wasm")]
     pub async fn render_to_canvas(self, canvas: impl ToString) {
         let canvas = canvas.to_string();
@@ -0,0 +1,374 @@
+//! # Firmware Simulator
+//! membangkitkan deret [`SerialData`] sintetis dari satu run motor di atas dyno,
+//! tanpa perlu hardware. model yang digunakan:
+//! - throttle ramp dari idle sampai WOT (wide open throttle)
+//! - kurva torsi engine dari [`MotorInfo`] dan perpindahan gigi otomatis
+//! - inertia roller beserta rugi-rugi gesekan (coulomb + viscous)
+//! - coastdown dengan kopling terbuka setelah pull selesai
+//!
+//! hasil simulasi deterministik untuk `seed` yang sama, sehingga bisa dipakai di CI.
+//! ```
+//! use dyno_core::{simulator::DynoSimulator, BufferData, DynoConfig};
+//! let mut config = DynoConfig::default();
+//! let mut buffer = BufferData::new();
+//! for serial in DynoSimulator::from_config(&config) {
+//!     buffer.push_from_serial(&mut config, serial);
+//! }
+//! assert!(buffer.speed.max_value().value() > 50.0);
+//! ```
+use crate::{
+    convertions::prelude::*, DynoConfig, DynoResult, Float, MinMaxNumeric, MotorInfo, MotorType,
+    SerialData, Stroke, PI,
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct SimulatorConfig {
+    /// periode pengiriman data dari firmware (ms)
+    pub period_ms: u32,
+    /// variasi acak periode (ms), `0` untuk periode tetap
+    pub period_jitter_ms: u32,
+    /// jumlah pulse encoder dalam satu putaran roller
+    pub encoder_ppr: u32,
+    /// seed untuk random generator (jitter)
+    pub seed: u64,
+
+    /// lama idle sebelum throttle dibuka (detik)
+    pub idle_time: Float,
+    /// lama throttle dibuka dari 0 sampai WOT (detik)
+    pub ramp_time: Float,
+    /// batas maksimal lama pull (detik)
+    pub max_pull_time: Float,
+    /// lama coastdown setelah pull selesai (detik)
+    pub coast_time: Float,
+    /// lama torsi terputus saat perpindahan gigi (detik)
+    pub shift_time: Float,
+
+    pub idle_rpm: RotationPerMinute,
+    pub peak_torque_rpm: RotationPerMinute,
+    pub shift_rpm: RotationPerMinute,
+    pub redline_rpm: RotationPerMinute,
+    pub peak_torque: NewtonMeter,
+
+    pub gear_ratios: Vec<Float>,
+    /// rasio primary x final drive
+    pub final_ratio: Float,
+    /// efisiensi drivetrain (0.0 - 1.0)
+    pub efficiency: Float,
+    pub wheel_radius: Metres,
+
+    /// inertia efektif pada poros roller (kg.m²)
+    pub roller_inertia: Float,
+    /// gesekan coulomb pada roller (Nm)
+    pub friction_coulomb: Float,
+    /// gesekan viscous pada roller (Nm.s/rad)
+    pub friction_viscous: Float,
+
+    pub ambient: Celcius,
+}
+
+impl Default for SimulatorConfig {
+    fn default() -> Self {
+        Self {
+            period_ms: 50,
+            period_jitter_ms: 0,
+            encoder_ppr: 360,
+            seed: 0x00D1_7E57,
+            idle_time: 2.0,
+            ramp_time: 0.5,
+            max_pull_time: 60.0,
+            coast_time: 5.0,
+            shift_time: 0.2,
+            idle_rpm: RotationPerMinute(1_500.),
+            peak_torque_rpm: RotationPerMinute(6_500.),
+            shift_rpm: RotationPerMinute(9_000.),
+            redline_rpm: RotationPerMinute(9_500.),
+            peak_torque: NewtonMeter(10.5),
+            gear_ratios: vec![2.833, 1.706, 1.250, 0.961],
+            final_ratio: 9.09,
+            efficiency: 0.9,
+            wheel_radius: Metres(0.28),
+            roller_inertia: 0.35,
+            friction_coulomb: 0.5,
+            friction_viscous: 0.02,
+            ambient: Celcius(30.),
+        }
+    }
+}
+
+impl SimulatorConfig {
+    /// ## estimate engine parameter from [`MotorInfo`] and roller from [`DynoConfig`]
+    pub fn from_config(config: &DynoConfig) -> Self {
+        Self {
+            peak_torque: Self::estimate_peak_torque(&config.motor_info),
+            roller_inertia: config.effective_inertia(),
+            ..Default::default()
+        }
+    }
+
+    /// estimasi kasar torsi puncak dari kapasitas mesin (~0.085 Nm/cc)
+    #[inline]
+    pub fn estimate_peak_torque(info: &MotorInfo) -> NewtonMeter {
+        NewtonMeter(info.cc as Float * 0.085)
+    }
+
+    /// ## engine torque at `rpm` with full throttle
+    pub fn torque_curve(&self, rpm: RotationPerMinute) -> NewtonMeter {
+        let span = (self.redline_rpm - self.idle_rpm).value();
+        if span <= 0.0 || rpm > self.redline_rpm * 1.05 {
+            return NewtonMeter(0.0);
+        }
+        let x = (rpm - self.peak_torque_rpm).value() / span;
+        NewtonMeter((self.peak_torque.value() * (1.0 - x * x)).max(0.0))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulatorPhase {
+    Idle,
+    Pull,
+    Coast,
+    Finished,
+}
+
+/// # Dyno Simulator
+/// iterator dari [`SerialData`] yang dihasilkan oleh satu run simulasi
+#[derive(Debug, Clone)]
+pub struct DynoSimulator {
+    config: SimulatorConfig,
+    roller_radius: Float,
+    motor_type: MotorType,
+    motor_info: MotorInfo,
+
+    phase: SimulatorPhase,
+    time: Float,
+    phase_time: Float,
+    gear: usize,
+    shift_timer: Float,
+    roller_omega: Float,
+    engine_rpm: RotationPerMinute,
+    temperature: Float,
+
+    enc_acc: Float,
+    z_acc: Float,
+    rpm_acc: Float,
+    rng: u64,
+}
+
+impl DynoSimulator {
+    pub fn new(dyno: &DynoConfig, config: SimulatorConfig) -> Self {
+        Self {
+            roller_radius: dyno.diameter_roller.value() * 0.5,
+            motor_type: dyno.motor_type,
+            motor_info: dyno.motor_info.clone(),
+            phase: SimulatorPhase::Idle,
+            time: 0.0,
+            phase_time: 0.0,
+            gear: 0,
+            shift_timer: 0.0,
+            roller_omega: 0.0,
+            engine_rpm: config.idle_rpm,
+            temperature: config.ambient.value(),
+            enc_acc: 0.0,
+            z_acc: 0.0,
+            rpm_acc: 0.0,
+            rng: crate::ternary!((config.seed == 0)?(0x00D1_7E57): (config.seed)),
+            config,
+        }
+    }
+
+    #[inline]
+    pub fn from_config(dyno: &DynoConfig) -> Self {
+        Self::new(dyno, SimulatorConfig::from_config(dyno))
+    }
+
+    #[inline]
+    pub const fn phase(&self) -> SimulatorPhase {
+        self.phase
+    }
+
+    #[inline]
+    pub const fn is_finished(&self) -> bool {
+        matches!(self.phase, SimulatorPhase::Finished)
+    }
+
+    /// waktu simulasi dalam detik
+    #[inline]
+    pub const fn time(&self) -> Float {
+        self.time
+    }
+
+    /// gigi saat ini, dimulai dari `1`
+    #[inline]
+    pub const fn gear(&self) -> usize {
+        self.gear + 1
+    }
+
+    #[inline]
+    pub fn roller_speed(&self) -> KilometresPerHour {
+        MetresPerSecond(self.roller_omega * self.roller_radius).to_kilometres_per_hour()
+    }
+
+    #[inline]
+    pub const fn engine_rpm(&self) -> RotationPerMinute {
+        self.engine_rpm
+    }
+
+    // xorshift64*, cukup untuk jitter yang deterministik
+    fn next_random(&mut self) -> u64 {
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn next_period(&mut self) -> u32 {
+        let jitter = self.config.period_jitter_ms;
+        if jitter == 0 {
+            return Ord::max(self.config.period_ms, 1);
+        }
+        let offset = (self.next_random() % (2 * jitter as u64 + 1)) as i64 - jitter as i64;
+        Ord::max(self.config.period_ms as i64 + offset, 1) as u32
+    }
+
+    fn throttle(&self) -> Float {
+        match self.phase {
+            SimulatorPhase::Pull if self.shift_timer <= 0.0 => {
+                if self.config.ramp_time <= 0.0 {
+                    return 1.0;
+                }
+                (self.phase_time / self.config.ramp_time).clamp(0.0, 1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    fn set_phase(&mut self, phase: SimulatorPhase) {
+        self.phase = phase;
+        self.phase_time = 0.0;
+    }
+
+    /// ## advance the simulation by one firmware period
+    /// return `None` when the run is finished
+    pub fn step(&mut self) -> Option<SerialData> {
+        if self.is_finished() {
+            return None;
+        }
+        let period = self.next_period();
+        let dt = period as Float * 0.001;
+        let cfg = &self.config;
+
+        let ratio = cfg.gear_ratios.get(self.gear).copied().unwrap_or(1.0) * cfg.final_ratio;
+        let roller_to_wheel = self.roller_radius / cfg.wheel_radius.value();
+        let coupled_rpm =
+            RadiansPerSecond(self.roller_omega * roller_to_wheel * ratio).to_rotation_per_minute();
+
+        let throttle = self.throttle();
+        let drive_torque = match self.phase {
+            SimulatorPhase::Pull => {
+                // kopling sentrifugal slip di bawah rpm idle
+                self.engine_rpm = coupled_rpm.max(cfg.idle_rpm);
+                cfg.torque_curve(self.engine_rpm).value()
+                    * throttle
+                    * ratio
+                    * cfg.efficiency
+                    * roller_to_wheel
+            }
+            _ => {
+                self.engine_rpm = cfg.idle_rpm;
+                0.0
+            }
+        };
+        let friction = if self.roller_omega > 0.0 {
+            cfg.friction_coulomb + cfg.friction_viscous * self.roller_omega
+        } else {
+            0.0
+        };
+        let alpha = (drive_torque - friction) / cfg.roller_inertia.max(Float::EPSILON);
+        let omega_prev = self.roller_omega;
+        self.roller_omega = (self.roller_omega + alpha * dt).max(0.0);
+
+        // putaran roller dalam satu periode
+        let revs = (omega_prev + self.roller_omega) * 0.5 * dt / (2.0 * PI);
+        self.enc_acc += revs * cfg.encoder_ppr as Float;
+        let pulse_enc = self.enc_acc.floor();
+        self.enc_acc -= pulse_enc;
+        self.z_acc += revs;
+        let pulse_enc_z = self.z_acc.floor();
+        self.z_acc -= pulse_enc_z;
+
+        let engine_revs = self.engine_rpm.value() / 60.0 * dt;
+        let pulse_per_rev = match (self.motor_type, self.motor_info.stroke) {
+            (MotorType::Engine, Stroke::Four) => self.motor_info.cylinder as u8 as Float * 0.5,
+            _ => 1.0,
+        };
+        self.rpm_acc += engine_revs * pulse_per_rev;
+        let pulse_rpm = self.rpm_acc.floor();
+        self.rpm_acc -= pulse_rpm;
+
+        let heating = 0.8 * throttle - 0.01 * (self.temperature - cfg.ambient.value());
+        self.temperature += heating * dt;
+
+        self.time += dt;
+        self.phase_time += dt;
+        self.shift_timer = (self.shift_timer - dt).max(0.0);
+        let last_gear = cfg.gear_ratios.len().saturating_sub(1);
+        match self.phase {
+            SimulatorPhase::Idle if self.phase_time >= cfg.idle_time => {
+                self.set_phase(SimulatorPhase::Pull)
+            }
+            SimulatorPhase::Pull => {
+                if self.gear >= last_gear && self.engine_rpm >= cfg.redline_rpm
+                    || self.phase_time >= cfg.max_pull_time
+                {
+                    self.set_phase(SimulatorPhase::Coast);
+                } else if self.gear < last_gear && self.engine_rpm >= cfg.shift_rpm {
+                    self.gear += 1;
+                    self.shift_timer = self.config.shift_time;
+                }
+            }
+            SimulatorPhase::Coast if self.phase_time >= cfg.coast_time => {
+                self.set_phase(SimulatorPhase::Finished)
+            }
+            _ => {}
+        }
+
+        Some(SerialData {
+            period,
+            pulse_enc_max: self.config.encoder_ppr,
+            pulse_enc: pulse_enc as u32,
+            pulse_enc_z: pulse_enc_z as u32,
+            pulse_rpm: pulse_rpm as u32,
+            temperature: self.temperature as f32,
+        })
+    }
+
+    /// ## write the remaining run as framed bytes into `writer`
+    /// if `realtime` is true, sleep for each period to mimic the firmware timing
+    /// (e.g. when writing into a pseudo-terminal). return the number of frames written
+    pub fn write_to<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        realtime: bool,
+    ) -> DynoResult<usize> {
+        let mut count = 0;
+        while let Some(data) = self.step() {
+            writer.write_all(&data.to_frame())?;
+            if realtime {
+                writer.flush()?;
+                std::thread::sleep(std::time::Duration::from_millis(data.period as u64));
+            }
+            count += 1;
+        }
+        writer.flush()?;
+        Ok(count)
+    }
+}
+
+impl Iterator for DynoSimulator {
+    type Item = SerialData;
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step()
+    }
+}
@@ -0,0 +1,421 @@
+use dyno_core::*;
+
+const PERIOD_MS: i64 = 50;
+
+/// build buffer from a rpm roda profile, with fixed sample period
+fn buffer_from_profile(profile: impl IntoIterator<Item = Float>) -> BufferData {
+    let mut buffer = BufferData::new();
+    let data = profile
+        .into_iter()
+        .enumerate()
+        .map(|(idx, rpm)| Data {
+            rpm_roda: RotationPerMinute(rpm),
+            rpm_engine: RotationPerMinute(rpm * 3.0),
+            speed: KilometresPerHour(rpm * 0.0268),
+            torque: NewtonMeter(10.0 + rpm * 0.001),
+            horsepower: HorsePower(rpm * 0.005),
+            time_stamp: chrono::NaiveDateTime::from_timestamp_millis(idx as i64 * PERIOD_MS)
+                .unwrap(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    buffer.extend_data(data);
+    buffer
+}
+
+fn ramp(from: Float, to: Float, n: usize) -> impl Iterator<Item = Float> {
+    (0..n).map(move |i| from + (to - from) * i as Float / n as Float)
+}
+
+fn two_pulls_profile() -> Vec<Float> {
+    std::iter::repeat_n(0.0, 20)
+        .chain(ramp(400.0, 3000.0, 60))
+        .chain(ramp(3000.0, 0.0, 40))
+        .chain(std::iter::repeat_n(500.0, 40))
+        .chain(ramp(500.0, 3500.0, 80))
+        .chain(ramp(3500.0, 300.0, 40))
+        .collect()
+}
+
+#[test]
+fn test_detect_pulls() {
+    let buffer = buffer_from_profile(two_pulls_profile());
+    let pulls = buffer.detect_pulls(&PullConfig::default());
+    assert_eq!(pulls.len(), 2);
+
+    assert!((18..=22).contains(&pulls[0].start), "{:?}", pulls[0]);
+    assert_eq!(pulls[0].end, 81);
+    assert!((158..=162).contains(&pulls[1].start), "{:?}", pulls[1]);
+    assert_eq!(pulls[1].end, 241);
+    assert_eq!(
+        pulls[1].duration_ms,
+        (pulls[1].len() as i64 - 1) * PERIOD_MS
+    );
+    assert_eq!(pulls[1].peak_horsepower_index, 240);
+
+    let best = buffer.best_pull(&PullConfig::default()).unwrap();
+    assert_eq!(best, pulls[1]);
+}
+
+#[test]
+fn test_detect_pulls_gear_shift() {
+    // short dip in the middle of the pull must not split it
+    let profile = ramp(400.0, 2000.0, 40)
+        .chain([1950.0, 1940.0, 1960.0])
+        .chain(ramp(2000.0, 3000.0, 40))
+        .chain(std::iter::repeat_n(0.0, 5));
+    let buffer = buffer_from_profile(profile);
+    let pulls = buffer.detect_pulls(&PullConfig::default());
+    assert_eq!(pulls.len(), 1);
+    assert_eq!(pulls[0].range(), 0..83);
+}
+
+#[test]
+fn test_power_curve() {
+    let profile = ramp(400.0, 3000.0, 260).chain(std::iter::repeat_n(0.0, 5));
+    let buffer = buffer_from_profile(profile);
+    let pull = buffer.best_pull(&PullConfig::default()).unwrap();
+    let curve = PowerCurve::from_pull(
+        &buffer,
+        &pull,
+        PowerCurve::DEFAULT_BIN_SIZE,
+        RpmSource::Engine,
+    );
+
+    // engine rpm 1200 - 9000 in 100 rpm bins
+    assert_eq!(curve.points.first().unwrap().rpm, RotationPerMinute(1200.0));
+    assert_eq!(curve.points.last().unwrap().rpm, RotationPerMinute(9000.0));
+    assert_eq!(curve.len(), 79);
+    assert!(curve.points.iter().all(|p| p.samples > 0));
+    assert!(curve
+        .points
+        .windows(2)
+        .all(|w| w[1].rpm.value() - w[0].rpm.value() == 100.0));
+
+    let peak_hp = curve.peak_horsepower().unwrap();
+    assert_eq!(peak_hp.rpm, RotationPerMinute(9000.0));
+    let peak_torque = curve.peak_torque().unwrap();
+    assert_eq!(peak_torque.rpm, peak_hp.rpm);
+
+    let mid = curve.at_rpm(RotationPerMinute(4550.0)).unwrap();
+    let (lo, hi) = (
+        curve.at_rpm(RotationPerMinute(4500.0)).unwrap(),
+        curve.at_rpm(RotationPerMinute(4600.0)).unwrap(),
+    );
+    assert!(lo.torque < mid.torque && mid.torque < hi.torque);
+    assert!(curve.at_rpm(RotationPerMinute(100.0)).is_none());
+}
+
+#[test]
+fn test_power_curve_interpolate_gap() {
+    let buffer = buffer_from_profile([1000.0, 1000.0, 2000.0, 2000.0]);
+    let curve = PowerCurve::from_range(&buffer, 0..4, RotationPerMinute(500.0), RpmSource::Roda);
+    let rpm = curve
+        .points
+        .iter()
+        .map(|p| p.rpm.value())
+        .collect::<Vec<_>>();
+    assert_eq!(rpm, [1000.0, 1500.0, 2000.0]);
+    assert_eq!(curve.points[1].samples, 0);
+    assert_eq!(curve.points[1].horsepower.value(), 7.5);
+}
+
+#[test]
+fn test_atmospheric_correction() {
+    let mut config = DynoConfig {
+        correction: CorrectionStandard::SaeJ1349,
+        ambient: AmbientConditions {
+            temperature: Celcius(35.0),
+            pressure: KiloPascal(100.0),
+            humidity: 50.0,
+        },
+        ..Default::default()
+    };
+    let factor = config.correction_factor();
+    assert!(factor > 1.0);
+
+    let mut buffer = BufferData::new();
+    for serial in dyno_core::simulator::DynoSimulator::from_config(&config) {
+        buffer.push_from_serial(&mut config, serial);
+    }
+    assert_eq!(buffer.torque_corrected.len(), buffer.len());
+    assert!(buffer
+        .horsepower
+        .iter()
+        .zip(buffer.horsepower_corrected.iter())
+        .all(|(raw, corrected)| *corrected == *raw * factor));
+
+    buffer.apply_correction(1.0);
+    assert_eq!(*buffer.torque_corrected, *buffer.torque);
+}
+
+#[test]
+fn test_drivetrain_loss_coastdown() {
+    // constant deceleration of the roller, loss torque is constant, loss power linear to speed
+    let profile = ramp(400.0, 3000.0, 60)
+        .chain(ramp(3000.0, 0.0, 100))
+        .collect::<Vec<_>>();
+    let buffer = buffer_from_profile(profile);
+    let pull_config = PullConfig::default();
+    let pull = buffer.best_pull(&pull_config).unwrap();
+    let coastdown = buffer.detect_coastdown(&pull, &pull_config).unwrap();
+    assert_eq!(coastdown.start, pull.end - 1);
+    assert!(buffer.speed[coastdown.end] < pull_config.min_speed);
+
+    let config = DynoConfig::default();
+    let loss = buffer
+        .estimate_drivetrain_loss(&config, coastdown.clone())
+        .unwrap();
+    assert!(loss.samples > 50);
+    assert!(loss.r_squared > 0.999, "{loss:?}");
+
+    let alpha =
+        RotationPerMinute(30.0).to_radians_per_second().value() / (PERIOD_MS as Float * 0.001);
+    let torque = config.roller_torque(alpha);
+    let expected = HorsePower::from_nm(torque, RotationPerMinute(2000.0));
+    let actual = loss.loss_at(KilometresPerHour(2000.0 * 0.0268));
+    assert!((actual.value() - expected.value()).abs() < expected.value() * 1e-5);
+
+    let crank = buffer.crank_horsepower(&loss);
+    assert_eq!(crank.len(), buffer.len());
+    assert!(crank
+        .iter()
+        .zip(buffer.horsepower.iter())
+        .all(|(crank, wheel)| crank >= wheel));
+    assert_eq!(
+        buffer.estimate_drivetrain_loss_auto(&config, &pull_config),
+        Some(loss)
+    );
+}
+
+/// rig dengan inertia terukur, sama dengan inertia roller pada simulator
+fn measured_rig(inertia: Float) -> DynoConfig {
+    DynoConfig {
+        inertia: InertiaModel {
+            components: vec![InertiaComponent {
+                name: "Roller".to_owned(),
+                shape: InertiaShape::Measured { moment: inertia },
+                ratio: 1.0,
+            }],
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_drivetrain_loss_filtered_recording() {
+    use dyno_core::simulator::*;
+
+    // filter realtime default aktif, rugi-rugi dari gesekan roller simulator
+    let mut config = measured_rig(0.35);
+    let sim = SimulatorConfig {
+        coast_time: 20.0,
+        ..SimulatorConfig::from_config(&config)
+    };
+    let mut buffer = BufferData::new();
+    buffer.raw.enabled = true;
+    for serial in DynoSimulator::new(&config, sim.clone()) {
+        buffer.push_from_serial(&mut config, serial);
+    }
+    let pull_config = PullConfig::default();
+    let loss = buffer
+        .estimate_drivetrain_loss_auto(&config, &pull_config)
+        .unwrap();
+
+    let radius = config.diameter_roller.value() * 0.5;
+    let expected = |speed: KilometresPerHour| {
+        let omega = speed.to_metres_per_second().value() / radius;
+        let torque = NewtonMeter(sim.friction_coulomb + sim.friction_viscous * omega);
+        HorsePower::from_nm(torque, RadiansPerSecond(omega).to_rotation_per_minute())
+    };
+    for speed in [30.0, 50.0, 70.0].map(KilometresPerHour) {
+        let (actual, expected) = (loss.loss_at(speed), expected(speed));
+        let error = (actual.value() - expected.value()).abs() / expected.value();
+        assert!(error < 0.05, "{speed}: {actual} != {expected}");
+    }
+}
+
+/// exact solution of `I * dω/dt = torque - c0 - c1 * ω`, sampled every `period_ms`
+fn roller_run(
+    inertia: Float,
+    torque: Float,
+    omega0: Float,
+    period_ms: i64,
+    len: usize,
+) -> BufferData {
+    const C0: Float = 0.5;
+    const C1: Float = 0.02;
+    let steady = (torque - C0) / C1;
+    let data = (0..len)
+        .map(|idx| {
+            let t = (idx as i64 * period_ms) as Float * 0.001;
+            let omega = steady + (omega0 - steady) * (-C1 * t / inertia).exp();
+            Data {
+                rpm_roda: RadiansPerSecond(omega.max(0.0)).to_rotation_per_minute(),
+                time_stamp: chrono::NaiveDateTime::from_timestamp_millis(
+                    1_000 + idx as i64 * period_ms,
+                )
+                .unwrap(),
+                ..Default::default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut buffer = BufferData::new();
+    buffer.extend_data(data);
+    buffer
+}
+
+#[test]
+fn test_inertia_calibration_added_mass() {
+    let free = roller_run(0.4, 0.0, 150.0, 20, 500);
+    let loaded = roller_run(0.4 + 0.25, 0.0, 150.0, 20, 500);
+    let mut config = DynoConfig::default();
+
+    let err = CalibrationResult::fit(&config, &[CalibrationRun::spin_down(&free)]).unwrap_err();
+    assert!(matches!(err.kind, ErrKind::Validation));
+
+    let result = CalibrationResult::fit(
+        &config,
+        &[
+            CalibrationRun::spin_down(&free),
+            CalibrationRun::with_added_inertia(&loaded, 0.25),
+        ],
+    )
+    .unwrap();
+    assert!((result.inertia - 0.4).abs() < 0.004, "{result:?}");
+    assert!((result.friction_coulomb - 0.5).abs() < 0.01, "{result:?}");
+    assert!((result.friction_viscous - 0.02).abs() < 0.001, "{result:?}");
+    assert!(result.r_squared > 0.99);
+
+    result.apply_to(&mut config);
+    assert_eq!(config.effective_inertia(), result.inertia);
+    assert_eq!(config.calibration, Some(result));
+}
+
+#[test]
+fn test_inertia_calibration_reference_torque() {
+    let spin_up = roller_run(0.4, 3.0, 0.0, 20, 300);
+    let spin_down = roller_run(0.4, 0.0, 120.0, 20, 300);
+    let config = DynoConfig::default();
+    let result = CalibrationResult::fit(
+        &config,
+        &[
+            CalibrationRun::with_torque(&spin_up, NewtonMeter(3.0)).range(1..300),
+            CalibrationRun::spin_down(&spin_down),
+        ],
+    )
+    .unwrap();
+    assert!((result.inertia - 0.4).abs() < 0.004, "{result:?}");
+}
+
+#[test]
+fn test_inertia_calibration_filtered_recording() {
+    use dyno_core::simulator::*;
+
+    // coastdown rig tanpa dan dengan massa kalibrasi, direkam dengan filter realtime default
+    let record = |roller_inertia: Float| {
+        let mut config = DynoConfig::default();
+        let sim = SimulatorConfig {
+            roller_inertia,
+            coast_time: 20.0,
+            ..SimulatorConfig::from_config(&config)
+        };
+        let mut buffer = BufferData::new();
+        buffer.raw.enabled = true;
+        for serial in DynoSimulator::new(&config, sim) {
+            buffer.push_from_serial(&mut config, serial);
+        }
+        let pull_config = PullConfig::default();
+        let pull = buffer.best_pull(&pull_config).unwrap();
+        let coastdown = buffer.detect_coastdown(&pull, &pull_config).unwrap();
+        (buffer, coastdown)
+    };
+    let (free, free_range) = record(0.35);
+    let (loaded, loaded_range) = record(0.35 + 0.25);
+
+    let config = DynoConfig::default();
+    let result = CalibrationResult::fit(
+        &config,
+        &[
+            CalibrationRun::spin_down(&free).range(free_range),
+            CalibrationRun::with_added_inertia(&loaded, 0.25).range(loaded_range),
+        ],
+    )
+    .unwrap();
+    assert!((result.inertia - 0.35).abs() < 0.35 * 0.03, "{result:?}");
+    assert!(result.r_squared > 0.95, "{result:?}");
+}
+
+#[test]
+fn test_zero_phase_smoothing() {
+    let mut config = DynoConfig::default();
+    let mut buffer = BufferData::new();
+    buffer.raw.enabled = true;
+    // putaran roller naik lalu turun, puncak pada sample 150
+    for idx in 0..300 {
+        let bump = (-((idx as Float - 150.0) / 40.0).powi(2)).exp();
+        let pulse_enc = (200.0 + 600.0 * bump) as u32;
+        let serial = SerialData {
+            period: 20,
+            pulse_enc,
+            pulse_enc_max: 360,
+            pulse_enc_z: pulse_enc / 360,
+            ..Default::default()
+        };
+        buffer.push_from_serial(&mut config, serial);
+    }
+    let peak = |values: &[RotationPerMinute]| {
+        (0..values.len())
+            .max_by(|a, b| values[*a].partial_cmp(&values[*b]).unwrap())
+            .unwrap()
+    };
+    // filter realtime (EMA period 100) menggeser puncak rpm
+    let live_rpm = buffer.rpm_roda.clone();
+    assert!(peak(&live_rpm) > 160, "live peak {}", peak(&live_rpm));
+
+    let kind = FilterKind::Butterworth {
+        order: ButterworthOrder::Second,
+        cutoff: 0.05,
+    };
+    buffer.smooth(&config, kind).unwrap();
+    assert_eq!(buffer.horsepower_smooth.len(), buffer.len());
+    assert_eq!(buffer.torque_smooth.len(), buffer.len());
+    assert_eq!(buffer.rpm_engine_smooth.len(), buffer.len());
+    let smooth_peak = peak(&buffer.rpm_roda_smooth);
+    assert!(
+        (smooth_peak as i64 - 150).abs() <= 2,
+        "smooth peak {smooth_peak}"
+    );
+    // kolom live tidak diubah
+    assert_eq!(*buffer.rpm_roda, *live_rpm);
+
+    // kurva daya memakai kolom smooth
+    let mut expected = buffer.clone();
+    expected.torque = buffer.torque_smooth.clone();
+    expected.horsepower = buffer.horsepower_smooth.clone();
+    expected.rpm_roda = buffer.rpm_roda_smooth.clone();
+    expected.torque_smooth.clear();
+    let bin = PowerCurve::DEFAULT_BIN_SIZE;
+    assert_eq!(
+        PowerCurve::from_range(&buffer, 0..buffer.len(), bin, RpmSource::Roda),
+        PowerCurve::from_range(&expected, 0..expected.len(), bin, RpmSource::Roda)
+    );
+
+    let decoded = BufferData::decompress(buffer.compress().unwrap()).unwrap();
+    assert_eq!(*decoded.rpm_roda_smooth, *buffer.rpm_roda_smooth);
+
+    // tanpa raw capture filtfilt memakai kolom tersimpan, lag filter realtime tetap ada
+    let mut csv = buffer.clone();
+    csv.raw = Default::default();
+    csv.smooth(&config, kind).unwrap();
+    assert_eq!(csv.rpm_roda_smooth.len(), csv.len());
+    let csv_peak = peak(&csv.rpm_roda_smooth);
+    assert!(
+        (csv_peak as i64 - peak(&live_rpm) as i64).abs() <= 2,
+        "csv peak {csv_peak}"
+    );
+
+    buffer.clean();
+    assert!(buffer.horsepower_smooth.is_empty());
+    assert!(buffer.rpm_roda_smooth.is_empty());
+}
@@ -35,3 +35,178 @@ fn test_buffer_minmax() {
     assert_eq!(BUFFER.min_value(), 69f64);
     assert_eq!(BUFFER.max_value(), 420f64);
 }
+
+#[test]
+fn test_buffer_ring() {
+    let mut buffer = Buf::ring(100);
+    buffer.extend((0..1000).map(|x| x as Float));
+    // hanya window terakhir yang tersimpan
+    assert!(
+        buffer.len() >= 100 && buffer.len() <= 112,
+        "{}",
+        buffer.len()
+    );
+    assert_eq!(buffer.evicted() + buffer.len(), 1000);
+    assert_eq!(buffer.last_value(), 999.0);
+    assert_eq!(buffer.max_value(), 999.0);
+    assert_eq!(buffer.min_value(), buffer.evicted() as Float);
+    let first = buffer.evicted() as Float;
+    assert_eq!(buffer.avg_value(), (first + 999.0) / 2.0);
+
+    // sumbu x tetap berdasarkan index sample asli
+    let points: Vec<[f64; 2]> = buffer.into_points(PointShowed::All);
+    assert_eq!(points[0], [first, first]);
+    assert_eq!(points.last(), Some(&[999.0, 999.0]));
+
+    buffer.clear();
+    assert_eq!(buffer.evicted(), 0);
+    assert!(buffer.ring_config().is_some());
+
+    // mengaktifkan ring mode pada buffer yang sudah berisi
+    let mut unbounded = BUFFER.clone();
+    unbounded.set_ring(Some(RingConfig {
+        capacity: 10,
+        overflow: RingOverflow::Evict,
+    }));
+    assert_eq!(unbounded.len(), 10);
+    assert_eq!(unbounded.evicted(), 90);
+}
+
+#[test]
+fn test_buffer_ring_spill() {
+    let path = std::env::temp_dir().join(format!("dyno_ring_spill_{}.bin", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut buffer = Buffer::<NewtonMeter>::ring_spill(50, &path);
+    for x in 0..500 {
+        buffer.push(NewtonMeter(x as Float));
+    }
+    let spilled = Buffer::<NewtonMeter>::read_spill(&path).unwrap();
+    assert_eq!(spilled.len(), buffer.evicted());
+    // sample yang di-spill dan yang tersimpan membentuk data lengkap
+    let all = spilled
+        .iter()
+        .chain(buffer.iter())
+        .map(|t| t.value())
+        .collect::<Vec<_>>();
+    assert_eq!(all, (0..500).map(|x| x as Float).collect::<Vec<_>>());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_buffer_downsample() {
+    let mut buffer = Buf::default();
+    buffer.extend((0..30_000).map(|x| (x as Float * 0.001).sin() * 100.0));
+    buffer[12_345] = 500.0;
+    buffer[23_456] = -500.0;
+
+    for showed in [PointShowed::Downsample(500), PointShowed::MinMax(500)] {
+        let points: Vec<[f64; 2]> = buffer.into_points(showed);
+        assert!(points.len() <= 500 && points.len() >= 400, "{showed}");
+        assert!(points.windows(2).all(|w| w[0][0] < w[1][0]), "{showed}");
+        // puncak tetap ada
+        assert!(points.contains(&[12_345.0, 500.0]), "{showed}");
+        assert!(points.contains(&[23_456.0, -500.0]), "{showed}");
+    }
+
+    let points: Vec<[f64; 2]> = buffer.into_points(PointShowed::Downsample(500));
+    assert_eq!(points.len(), 500);
+    assert_eq!(points.first(), Some(&[0.0, 0.0]));
+    assert_eq!(points.last(), Some(&[29_999.0, buffer.last_value()]));
+
+    // jumlah sample lebih sedikit dari threshold
+    let points: Vec<[f64; 2]> = BUFFER.into_points(PointShowed::Downsample(500));
+    assert_eq!(points.len(), BUFFER.len());
+    let mapped: Vec<[f64; 2]> = BUFFER.into_points_map(PointShowed::MinMax(10), |v| v * 2.0);
+    assert!(mapped.len() <= 10);
+    assert!(mapped.contains(&[99.0, 840.0]));
+
+    // threshold di bawah 3, hanya point pertama dan terakhir
+    let points = (0..10).map(|x| [x as f64, x as f64]).collect::<Vec<_>>();
+    for decimate in [lttb, min_max_decimate] {
+        assert!(decimate(&points, 0).is_empty());
+        assert_eq!(decimate(&points, 1), [points[0]]);
+        assert_eq!(decimate(&points, 2), [points[0], points[9]]);
+        assert!(decimate(&points[..2], 0).is_empty());
+        assert!((2..=3).contains(&decimate(&points, 3).len()));
+    }
+}
+
+#[test]
+fn test_buffer_integer_sum_saturate() {
+    // timestamp epoch ms, jumlah berjalan tidak overflow
+    let mut buffer = Buffer::<i64>::default();
+    buffer.extend([i64::MAX / 2, i64::MAX / 2, i64::MAX / 2]);
+    assert_eq!(buffer.sum_value(), i64::MAX);
+    assert_eq!(buffer.max_value(), i64::MAX / 2);
+
+    let mut buffer = Buffer::<i64>::default();
+    buffer.extend([i64::MIN / 2, i64::MIN / 2, i64::MIN / 2]);
+    assert_eq!(buffer.sum_value(), i64::MIN);
+}
+
+#[test]
+fn test_buffer_running_stats() {
+    let values = (0..1000)
+        .map(|x| ((x * 7919) % 1000) as Float * 0.1)
+        .collect::<Vec<_>>();
+    let mut buffer = Buf::default();
+    buffer.extend(values.iter().copied());
+
+    let mean = values.iter().sum::<Float>() / values.len() as Float;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<Float>() / values.len() as Float;
+    let stats = buffer.stats();
+    assert_eq!(stats.count, 1000);
+    assert_eq!(stats.min, 0.0);
+    assert!((stats.max - 99.9).abs() < 1e-9);
+    assert!((stats.mean - mean).abs() < 1e-9);
+    assert!((buffer.variance_value() - variance).abs() < 1e-9);
+    assert!((stats.std_dev() - variance.sqrt()).abs() < 1e-9);
+
+    // mutasi melalui DerefMut, statistik dihitung ulang
+    buffer[10] = 1000.0;
+    assert_eq!(buffer.max_value(), 1000.0);
+    buffer.retain(|v| *v < 50.0);
+    assert!(buffer.max_value() < 50.0 && buffer.max_value() > 49.8);
+    buffer.push(-1.0);
+    assert_eq!(buffer.min_value(), -1.0);
+    assert_eq!(buffer.stats().count, buffer.len());
+    assert_eq!(buffer.sum_value(), buffer.iter().sum::<Float>());
+
+    // hasil deserialisasi
+    let bytes = buffer.serialize_bin().unwrap();
+    let decoded = Buf::deserialize_bin(&bytes).unwrap();
+    assert_eq!(decoded.stats(), buffer.stats());
+    assert_eq!(Buf::from(buffer.to_vec()).stats(), buffer.stats());
+
+    buffer.clear();
+    assert_eq!(buffer.stats(), BufferStats::default());
+}
+
+#[test]
+fn test_buffer_descriptive_stats() {
+    // suhu 0.0 - 99.0 dengan satu puncak di akhir
+    let mut temp = Buffer::<Celcius>::default();
+    temp.extend((0..100).map(|x| Celcius(x as Float)));
+    assert_eq!(temp.median_value(), 49.5);
+    assert!((temp.percentile_value(95.0) - 94.05).abs() < 1e-9);
+    assert_eq!(temp.percentile_value(100.0), 99.0);
+
+    let histogram = temp.histogram(10);
+    assert_eq!(histogram.counts, [10; 10]);
+    assert_eq!(histogram.total(), temp.len());
+
+    // ripple torque +-2 di sekitar 50, rms ripple sama dengan std
+    let mut torque = Buffer::<NewtonMeter>::default();
+    torque.extend((0..200).map(|x| NewtonMeter(ternary!((x % 2 == 0)?(52.0): (48.0)))));
+    assert_eq!(torque.std_value(), 2.0);
+    assert!((torque.rms_value() - (50.0f64.powi(2) + 4.0).sqrt()).abs() < 1e-9);
+
+    // statistik pada sebagian index
+    assert_eq!(BUFFER.stats_in(..99).max, 69.0);
+    assert_eq!(BUFFER.stats_in(98..).max, 420.0);
+    assert_eq!(BUFFER.stats_in(98..).count, 2);
+    assert_eq!(BUFFER.distribution(90..=99).percentile(100.0), 420.0);
+    assert_eq!(BUFFER.distribution(..).median(), BUFFER.median_value());
+    assert!(BUFFER.distribution(200..300).is_empty());
+    assert_eq!(BUFFER.stats_in(200..).count, 0);
+}
@@ -83,17 +83,41 @@ fn test_data_buffer() {
     assert_eq!(DEFAULT_DATA_BUFFER.len(), SIZE_TESTED);
     let data = DEFAULT_DATA_BUFFER.last();
     asserts_data!(data);
+    // constant roller speed, no angular acceleration from the first sample
     assert_eq!(
-        DEFAULT_DATA_BUFFER.torque.first_value().round_decimal(1),
-        48.1,
+        DEFAULT_DATA_BUFFER.torque.max_value().round_decimal(1),
+        0.0,
         "data torque asserts"
     );
+    let time = &DEFAULT_DATA_BUFFER.time_stamp;
+    assert!(
+        time.windows(2)
+            .all(|w| w[1] - w[0] == SER_DATA.period as i64),
+        "timestamp follow the firmware period"
+    );
+
+    // roller spin-up from rest, the estimator has the rest sample as history.
+    // α dalam rad/s², 5x torsi lama per-sample (48.1 Nm, 17.6 HP) pada period 200 ms
+    let mut config = DynoConfig::default();
+    config.filter = DataFilter::with_kind(FilterKind::None);
+    config.derivative = DerivativeEstimator::new(DerivativeKind::Backward);
+    let mut spin_up = BufferData::new();
+    let rest = SerialData {
+        pulse_enc: 0,
+        pulse_rpm: 0,
+        pulse_enc_z: 0,
+        ..SER_DATA
+    };
+    spin_up.push_from_serial(&mut config, rest);
+    spin_up.push_from_serial(&mut config, SER_DATA);
     assert_eq!(
-        DEFAULT_DATA_BUFFER
-            .horsepower
-            .first_value()
-            .round_decimal(1),
-        17.6,
+        spin_up.torque[1].round_decimal(1),
+        240.5,
+        "data torque asserts"
+    );
+    assert_eq!(
+        spin_up.horsepower[1].round_decimal(1),
+        88.1,
         "data horsepower asserts"
     );
 }
@@ -119,6 +143,10 @@ fn test_open_compressed() {
     assert_eq!(buffer_data.len(), SIZE_TESTED);
     let data = buffer_data.last();
     asserts_data!(data);
+    // `test_bin.dyno` ditulis dengan layout lama (tanpa header versi)
+    assert!(buffer_data.raw.is_empty());
+    assert_eq!(buffer_data.quality.len(), SIZE_TESTED);
+    assert_eq!(*buffer_data.torque_corrected, *buffer_data.torque);
 }
 #[test]
 fn test_compressed_data_buffer() {
@@ -126,6 +154,34 @@ fn test_compressed_data_buffer() {
     test_open_compressed();
 }
 
+#[test]
+fn test_compressed_format_version() {
+    let bytes = DEFAULT_DATA_BUFFER
+        .serialize_bin()
+        .expect("serialize buffer");
+    assert_eq!(&bytes[..8], b"DYNOFILE");
+    assert_eq!(bytes[8..12], DYNO_FORMAT_VERSION.to_le_bytes());
+
+    let decoded = BufferData::decompress(DEFAULT_DATA_BUFFER.compress().unwrap()).unwrap();
+    assert_eq!(decoded.len(), SIZE_TESTED);
+    assert_eq!(
+        *decoded.torque_corrected,
+        *DEFAULT_DATA_BUFFER.torque_corrected
+    );
+    assert_eq!(decoded.quality, DEFAULT_DATA_BUFFER.quality);
+
+    // versi 1 tanpa frame warm-up setelah body
+    let mut v1 = bytes.clone();
+    v1[8..12].copy_from_slice(&1u32.to_le_bytes());
+    let decoded = BufferData::deserialize_bin(&v1).unwrap();
+    assert_eq!(decoded.len(), SIZE_TESTED);
+    assert!(decoded.raw.warmup.is_empty());
+
+    let mut future = bytes;
+    future[8..12].copy_from_slice(&(DYNO_FORMAT_VERSION + 1).to_le_bytes());
+    assert!(BufferData::deserialize_bin(&future).is_err());
+}
+
 fn test_save_csv() {
     let path = PathBuf::from(MANIFEST_DIR).join("tests/files/test_csv.csv");
     if !path.exists() {
@@ -190,3 +246,434 @@ fn test_excel_data_buffer() {
     test_save_excel();
     test_open_excel();
 }
+
+#[test]
+fn test_serial_v2_channels() {
+    let mut config = DynoConfig::default();
+    let mut buffer = BufferData::new();
+    for idx in 0..10 {
+        let serial = SerialDataV2 {
+            base: SER_DATA,
+            temperature_2: 30.0 + idx as f32,
+            load_cell: 2.5 * idx as f32,
+        };
+        buffer.push_from_serial(&mut config, serial);
+    }
+    buffer.push_from_serial(&mut config, SER_DATA);
+    assert_eq!(buffer.temp_2.len(), buffer.len());
+    assert_eq!(buffer.temp_2[9], Celcius::new(39.0));
+    assert_eq!(buffer.load_cell[9], KiloGram::new(22.5));
+    // payload V1 tidak membawa channel tambahan, nilai terakhir dipertahankan
+    assert_eq!(buffer.load_cell[10], KiloGram::new(22.5));
+
+    let decoded = BufferData::decompress(buffer.compress().unwrap()).unwrap();
+    assert_eq!(*decoded.temp_2, *buffer.temp_2);
+    assert_eq!(*decoded.load_cell, *buffer.load_cell);
+    assert_eq!(decoded.last().load_cell, KiloGram::new(22.5));
+
+    let csv = BufferData::open_csv_from_bytes(buffer.save_csv_into_bytes().unwrap()).unwrap();
+    assert_eq!(*csv.load_cell, *buffer.load_cell);
+    assert_eq!(*csv.temp_2, *buffer.temp_2);
+
+    // file versi 3 tanpa kolom channel V2
+    let mut v3 = buffer.serialize_bin().unwrap();
+    v3[8..12].copy_from_slice(&3u32.to_le_bytes());
+    let decoded = BufferData::deserialize_bin(&v3).unwrap();
+    assert_eq!(decoded.load_cell.len(), buffer.len());
+    assert_eq!(decoded.load_cell.max_value(), KiloGram::default());
+}
+
+#[test]
+fn test_raw_capture_recompute() {
+    let mut config = DynoConfig::default();
+    let mut buffer = BufferData::new();
+    buffer.raw.enabled = true;
+    for idx in 0..100u32 {
+        let serial = SerialData {
+            pulse_enc: 1000 + idx * 10,
+            ..SER_DATA
+        };
+        buffer.push_from_serial(&mut config, serial);
+    }
+    assert_eq!(buffer.raw.len(), buffer.len());
+
+    let bytes = buffer.compress().unwrap();
+    let mut decoded = BufferData::decompress(bytes).unwrap();
+    assert_eq!(decoded.raw, buffer.raw);
+
+    // same config, same result
+    decoded.recompute(&mut config).unwrap();
+    assert_eq!(*decoded.speed, *buffer.speed);
+    assert_eq!(*decoded.torque, *buffer.torque);
+    assert_eq!(*decoded.time_stamp, *buffer.time_stamp);
+    assert_eq!(decoded.raw, buffer.raw);
+
+    // fix roller diameter after the fact
+    config.diameter_roller = config.diameter_roller * 0.5;
+    decoded.recompute(&mut config).unwrap();
+    assert_eq!(decoded.len(), buffer.len());
+    assert_eq!(
+        decoded.speed.last_value().round_decimal(2),
+        (buffer.speed.last_value() * 0.5).round_decimal(2)
+    );
+
+    let mut empty = BufferData::new();
+    assert!(empty.recompute(&mut config).is_err());
+}
+
+#[test]
+fn test_resume_after_gap() {
+    let new_config = || {
+        let mut config = DynoConfig::default();
+        config.filter = DataFilter::with_kind(FilterKind::TimeConstant { time_constant: 0.5 });
+        config.derivative = DerivativeEstimator::new(DerivativeKind::Central { window: 5 });
+        config
+    };
+    let serial = |idx: u32| {
+        let pulse_enc = 1000 + idx * 20;
+        SerialData {
+            pulse_enc,
+            pulse_enc_z: pulse_enc / 360,
+            ..SER_DATA
+        }
+    };
+
+    let mut config = new_config();
+    let mut buffer = BufferData::new();
+    buffer.data.sample_clock = chrono::NaiveDateTime::from_timestamp_millis(1_600_000_000_000);
+    (0..50).for_each(|idx| buffer.push_from_serial(&mut config, serial(idx)));
+    let snapshot = FilterSnapshot::decompress(config.snapshot().compress().unwrap()).unwrap();
+
+    // sesi yang tidak terhenti
+    let mut expected = buffer.clone();
+    (50..80).for_each(|idx| expected.push_from_serial(&mut config, serial(idx)));
+
+    // sesi baru setelah crash, timestamp tidak lagi bersambung dengan snapshot
+    let mut config = new_config();
+    config.resume(snapshot);
+    let mut resumed = BufferData::new();
+    (50..80).for_each(|idx| resumed.push_from_serial(&mut config, serial(idx)));
+
+    let gap = resumed.time_stamp[0] - buffer.time_stamp.last_value();
+    assert!(gap > 365 * 24 * 3600 * 1000);
+    for (idx, (e, a)) in expected.torque[50..]
+        .iter()
+        .zip(resumed.torque.iter())
+        .enumerate()
+    {
+        assert!(
+            (e.value() - a.value()).abs() < 1e-9,
+            "torque {idx}: {e} != {a}"
+        );
+    }
+    for (idx, (e, a)) in expected.rpm_roda[50..]
+        .iter()
+        .zip(resumed.rpm_roda.iter())
+        .enumerate()
+    {
+        assert!(
+            (e.value() - a.value()).abs() < 1e-9,
+            "rpm {idx}: {e} != {a}"
+        );
+    }
+}
+
+#[test]
+fn test_encoder_quality() {
+    let mut config = DynoConfig::default();
+    config.encoder.use_z_index = true;
+    let mut buffer = BufferData::new();
+    buffer.push_from_serial(&mut config, SER_DATA);
+    assert!(buffer.last().quality.is_ok());
+    let odo = buffer.last().odo;
+
+    // counter firmware wraparound, putaran dikoreksi dari Z index
+    let wrapped = SerialData {
+        pulse_enc: u32::MAX - 100,
+        ..SER_DATA
+    };
+    buffer.push_from_serial(&mut config, wrapped);
+    let data = buffer.last();
+    assert!(data
+        .quality
+        .contains(SampleQuality::ENCODER_WRAP | SampleQuality::CORRECTED));
+    assert!(data.odo > odo && data.odo < odo * 3.0, "odo {}", data.odo);
+
+    // pulse encoder terlewat, putaran dikoreksi menjadi 11 putaran (Z index)
+    let odo = buffer.last().odo;
+    let missed = SerialData {
+        pulse_enc: 3000,
+        ..SER_DATA
+    };
+    buffer.push_from_serial(&mut config, missed);
+    assert!(buffer.last().quality.contains(SampleQuality::MISSED_PULSE));
+    let expected = (config.keliling_roller * 11.0).to_kilometres();
+    assert!(((buffer.last().odo - odo) - expected).to_float().abs() < 1e-9);
+}
+
+#[test]
+fn test_sample_quality_outlier() {
+    let mut config = DynoConfig::default();
+    let mut buffer = BufferData::new();
+    for idx in 0..40u32 {
+        let serial = match idx {
+            // spike encoder pada sample 20, konsisten dengan Z index
+            20 => SerialData {
+                pulse_enc: 42000,
+                pulse_enc_z: 42000 / 360,
+                ..SER_DATA
+            },
+            // sensor suhu NaN
+            30 => SerialData {
+                temperature: f32::NAN,
+                ..SER_DATA
+            },
+            _ => SER_DATA,
+        };
+        buffer.push_from_serial(&mut config, serial);
+    }
+    assert_eq!(buffer.quality.len(), buffer.len());
+
+    // spike ditandai, nilainya tidak diubah
+    assert!(buffer.quality[20].contains(SampleQuality::OUTLIER));
+    assert!(buffer.speed[20] > buffer.speed[19] * 5.0);
+    let outliers = |buffer: &BufferData| {
+        buffer
+            .quality
+            .iter()
+            .filter(|q| q.contains(SampleQuality::OUTLIER))
+            .count()
+    };
+    assert_eq!(outliers(&buffer), 1);
+
+    // sensor suhu NaN, nilai sebelumnya dipakai
+    assert!(buffer.quality[30].contains(SampleQuality::SENSOR_FAULT | SampleQuality::SUBSTITUTED));
+    assert_eq!(buffer.temp[30], Celcius::new(420.));
+
+    // deteksi ulang seluruh buffer (mis. file lama)
+    buffer.quality.clear();
+    assert_eq!(buffer.detect_outliers(&config.quality.outlier), 1);
+    assert_eq!(outliers(&buffer), 1);
+    assert!(buffer.quality[20].contains(SampleQuality::OUTLIER));
+}
+
+#[test]
+fn test_ring_buffer_data() {
+    let dir = std::env::temp_dir().join(format!("dyno_ring_data_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let serial = |idx: u32| match idx % 37 {
+        // spike encoder, ditandai outlier
+        0 => SerialData {
+            pulse_enc: 42000,
+            pulse_enc_z: 42000 / 360,
+            ..SER_DATA
+        },
+        _ => SerialData {
+            pulse_enc: 4000 + (idx % 50) * 10,
+            ..SER_DATA
+        },
+    };
+
+    let mut config = DynoConfig::default();
+    let mut full = BufferData::new();
+    (0..1000).for_each(|idx| full.push_from_serial(&mut config, serial(idx)));
+
+    let mut config = DynoConfig::default();
+    let mut ring = BufferData::new();
+    ring.raw.enabled = true;
+    ring.set_ring(Some(RingConfig {
+        capacity: 200,
+        overflow: RingOverflow::Spill(dir.clone()),
+    }));
+    (0..1000).for_each(|idx| ring.push_from_serial(&mut config, serial(idx)));
+
+    let len = ring.len();
+    assert!((200..225).contains(&len), "{len}");
+    assert_eq!(ring.evicted() + len, 1000);
+    assert_eq!(ring.quality.len(), len);
+    assert_eq!(ring.raw.len(), len);
+    assert_eq!(ring.torque.len(), len);
+    assert_eq!(ring.load_cell.len(), len);
+    // kolom, kualitas dan tanda outlier tetap sejajar dengan rekaman tanpa ring
+    let tail = 1000 - len;
+    assert_eq!(*ring.torque, full.torque[tail..]);
+    assert_eq!(ring.quality, full.quality[tail..]);
+    assert!(ring
+        .quality
+        .iter()
+        .any(|q| q.contains(SampleQuality::OUTLIER)));
+
+    let spilled = Buffer::<KilometresPerHour>::read_spill(dir.join("speed.bin")).unwrap();
+    assert_eq!(spilled.len(), ring.evicted());
+    assert_eq!(spilled[..], full.speed[..tail]);
+
+    // frame raw yang keluar menjadi warm-up, recompute tetap sejajar
+    let mut recomputed = ring.clone();
+    recomputed.set_ring(None);
+    recomputed.recompute(&mut config).unwrap();
+    assert_eq!(*recomputed.time_stamp, *ring.time_stamp);
+    assert_eq!(*recomputed.speed, *ring.speed);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_slice_by_time() {
+    let buffer = &*DEFAULT_DATA_BUFFER;
+    let period = SER_DATA.period as i64;
+    let first = buffer.time_stamp[0];
+    assert_eq!(buffer.index_at_time(first), Some(0));
+    assert_eq!(buffer.index_at_time(first - 10_000), Some(0));
+    assert_eq!(buffer.index_at_time(first + period * 10 + 99), Some(10));
+    assert_eq!(buffer.index_at_time(first + period * 10 + 101), Some(11));
+    assert_eq!(buffer.index_at_time(i64::MAX), Some(SIZE_TESTED - 1));
+    assert_eq!(BufferData::new().index_at_time(first), None);
+
+    let slice = buffer.slice_by_time(first + period * 100, first + period * 199 + 1);
+    assert_eq!(
+        buffer.range_by_time(first + period * 100, first + period * 199),
+        100..200
+    );
+    assert_eq!(slice.len(), 100);
+    assert_eq!(slice.time_stamp.len(), 100);
+    assert_eq!(slice.quality.len(), 100);
+    assert_eq!(slice.time_stamp[0], buffer.time_stamp[100]);
+    assert_eq!(*slice.speed, buffer.speed[100..200]);
+    assert_eq!(
+        slice.last().time_stamp.timestamp_millis(),
+        buffer.time_stamp[199]
+    );
+    assert_eq!(slice.total_time, (period * 99) as u64);
+    assert!(slice.torque_smooth.is_empty());
+
+    // range di luar rekaman
+    assert!(buffer.slice_by_time(0, first - 1).is_empty());
+    assert!(buffer.slice_by_time(first + period * 10, first).is_empty());
+
+    // raw capture ikut dipotong dan bisa dihitung ulang
+    let mut config = DynoConfig::default();
+    let mut recorded = BufferData::new();
+    recorded.raw.enabled = true;
+    for _ in 0..50 {
+        recorded.push_from_serial(&mut config, SER_DATA);
+    }
+    let start = recorded.time_stamp[20];
+    let mut slice = recorded.slice_by_time(start, start + period * 9);
+    assert_eq!(slice.raw.len(), 10);
+    assert_eq!(slice.raw.start, start);
+    slice.recompute(&mut config).unwrap();
+    assert_eq!(slice.len(), 10);
+    assert_eq!(slice.time_stamp[0], start);
+}
+
+#[test]
+fn test_resample_uniform() {
+    // period firmware berubah-ubah 150 / 250 ms
+    let mut time_ms = 0;
+    let data = (0..40)
+        .map(|idx| {
+            time_ms += ternary!((idx % 2 == 0)?(150): (250));
+            let t = time_ms as Float * 0.001;
+            Data {
+                speed: KilometresPerHour(2.0 * t + 1.0),
+                torque: NewtonMeter(t * t),
+                time_stamp: chrono::NaiveDateTime::from_timestamp_millis(time_ms).unwrap(),
+                quality: ternary!((idx == 10)?(SampleQuality::OUTLIER): (SampleQuality::OK)),
+                ..Default::default()
+            }
+        })
+        .collect::<Vec<_>>();
+    let mut buffer = BufferData::new();
+    buffer.extend_data(&data);
+
+    let linear = buffer.resample(50.0, Interpolation::Linear).unwrap();
+    assert!(linear.time_stamp.windows(2).all(|w| w[1] - w[0] == 20));
+    assert_eq!(linear.time_stamp[0], buffer.time_stamp[0]);
+    // 150 ms - 8000 ms, titik terakhir tidak melewati sample terakhir
+    assert_eq!(linear.time_stamp.last_value(), 7990);
+    assert_eq!(linear.len(), 393);
+    assert_eq!(linear.speed.len(), linear.len());
+    assert_eq!(linear.quality.len(), linear.len());
+    assert!(linear.torque_smooth.is_empty());
+    assert!(linear
+        .speed
+        .iter()
+        .zip(linear.time_stamp.iter())
+        .all(|(v, t)| (v.value() - (2.0 * *t as Float * 0.001 + 1.0)).abs() < 1e-9));
+
+    // sample outlier ikut menandai titik di sekitarnya
+    let outlier = buffer.time_stamp[10];
+    let flagged = linear
+        .time_stamp
+        .iter()
+        .zip(linear.quality.iter())
+        .filter(|(_, q)| q.contains(SampleQuality::OUTLIER))
+        .map(|(t, _)| *t)
+        .collect::<Vec<_>>();
+    assert!(flagged.contains(&outlier));
+    assert!(flagged
+        .iter()
+        .all(|t| (buffer.time_stamp[9]..=buffer.time_stamp[11]).contains(t)));
+
+    let info = linear.resample_info.unwrap();
+    assert_eq!(info.rate_hz, 50.0);
+    assert_eq!(info.original_rate_hz, 39.0 * 1000.0 / 7850.0);
+    assert_eq!(info.original_len, 40);
+    assert_eq!(info.interpolation, Interpolation::Linear);
+
+    // cubic lebih akurat untuk kurva torque (kuadratik)
+    let cubic = buffer.resample(50.0, Interpolation::Cubic).unwrap();
+    let error = |resampled: &BufferData| {
+        resampled
+            .torque
+            .iter()
+            .zip(resampled.time_stamp.iter())
+            .map(|(v, t)| (v.value() - (*t as Float * 0.001).powi(2)).abs())
+            .fold(0.0, Float::max)
+    };
+    assert!(
+        error(&cubic) < error(&linear),
+        "{} {}",
+        error(&cubic),
+        error(&linear)
+    );
+
+    // resample ulang tetap menyimpan rate asli
+    let again = linear.resample(10.0, Interpolation::Linear).unwrap();
+    assert_eq!(
+        again.resample_info.unwrap().original_rate_hz,
+        info.original_rate_hz
+    );
+    assert_eq!(again.resample_info.unwrap().original_len, 40);
+
+    // rate tidak valid atau jumlah sample hasil terlalu besar
+    for rate_hz in [0.0, -50.0, Float::NAN, Float::INFINITY, 1e12] {
+        assert!(
+            buffer.resample(rate_hz, Interpolation::Linear).is_err(),
+            "{rate_hz}"
+        );
+    }
+
+    // timestamp epoch (ms) dari sesi live, hasil sama dengan waktu relatif
+    const EPOCH_MS: i64 = 1_760_000_000_000;
+    let live = data
+        .iter()
+        .map(|d| Data {
+            time_stamp: d.time_stamp + chrono::Duration::milliseconds(EPOCH_MS),
+            ..*d
+        })
+        .collect::<Vec<_>>();
+    let mut live_buffer = BufferData::new();
+    live_buffer.extend_data(&live);
+    let live = live_buffer.resample(50.0, Interpolation::Cubic).unwrap();
+    assert_eq!(*live.torque, *cubic.torque);
+    assert!(live
+        .time_stamp
+        .iter()
+        .zip(cubic.time_stamp.iter())
+        .all(|(live, relative)| live - relative == EPOCH_MS));
+
+    assert!(buffer.resample(0.0, Interpolation::Linear).is_err());
+    let mut single = BufferData::new();
+    single.extend_data(&data[..1]);
+    assert!(single.resample(50.0, Interpolation::Cubic).is_err());
+}
@@ -0,0 +1,121 @@
+use dyno_core::{simulator::*, *};
+
+#[test]
+fn test_session_states() {
+    let mut config = DynoConfig::default();
+    let sim = SimulatorConfig {
+        coast_time: 120.0,
+        ..SimulatorConfig::from_config(&config)
+    };
+    let mut session = DynoSession::new();
+    let mut events = Vec::new();
+    let mut total = 0;
+    for serial in DynoSimulator::new(&config, sim) {
+        if let Some(event) = session.push_from_serial(&mut config, serial) {
+            events.push(event);
+        }
+        total += 1;
+    }
+
+    let states = events.iter().map(SessionEvent::state).collect::<Vec<_>>();
+    assert_eq!(
+        states,
+        [
+            SessionState::Warmup,
+            SessionState::Armed,
+            SessionState::Recording,
+            SessionState::Coastdown,
+            SessionState::Finished,
+        ]
+    );
+    assert_eq!(session.state(), SessionState::Finished);
+
+    let buffer = session.buffer();
+    assert!(!buffer.is_empty() && buffer.len() < total);
+    assert!(matches!(
+        events[2],
+        SessionEvent::RecordingStarted { index: 0 }
+    ));
+    assert!(matches!(events[4], SessionEvent::Finished { len } if len == buffer.len()));
+
+    let coastdown = session.coastdown_index().unwrap();
+    assert!(buffer.rpm_roda[coastdown..]
+        .iter()
+        .all(|rpm| *rpm < buffer.rpm_roda.max_value()));
+}
+
+#[test]
+fn test_session_warmup() {
+    let mut config = DynoConfig::default();
+    config.session.warmup_ms = 1_000;
+    let data = SerialData {
+        period: 100,
+        pulse_enc_max: 360,
+        temperature: 60.0,
+        ..Default::default()
+    };
+    let mut session = DynoSession::new();
+    assert_eq!(
+        session.push_from_serial(&mut config, data),
+        Some(SessionEvent::WarmupStarted)
+    );
+    for _ in 0..9 {
+        assert_eq!(session.push_from_serial(&mut config, data), None);
+    }
+    assert_eq!(
+        session.push_from_serial(&mut config, data),
+        Some(SessionEvent::Armed)
+    );
+    assert!(session.buffer().is_empty());
+    assert!(session.finish().is_some());
+    assert_eq!(session.push_from_serial(&mut config, data), None);
+}
+
+#[test]
+fn test_session_raw_capture() {
+    let mut config = DynoConfig::default();
+    let mut session = DynoSession::new();
+    session.set_raw_capture(true);
+    for serial in DynoSimulator::from_config(&config) {
+        session.push_from_serial(&mut config, serial);
+    }
+    let buffer = session.buffer();
+    // sample pertama saat Armed -> Recording ikut direkam
+    assert!(!buffer.is_empty());
+    assert_eq!(buffer.raw.len(), buffer.len());
+    assert_eq!(buffer.raw.start, buffer.time_stamp[0]);
+
+    // sample warm-up ikut direkam untuk menghangatkan filter saat dihitung ulang
+    assert!(!buffer.raw.warmup.is_empty());
+
+    let live = session.into_buffer();
+    let mut recomputed = BufferData::decompress(live.compress().unwrap()).unwrap();
+    let mut config = DynoConfig::default();
+    recomputed.recompute(&mut config).unwrap();
+    assert_eq!(recomputed.len(), live.len());
+    assert_eq!(recomputed.raw, live.raw);
+    assert_eq!(*recomputed.time_stamp, *live.time_stamp);
+    // config sama, hasil sama dengan saat live
+    assert_eq!(*recomputed.torque, *live.torque);
+    assert_eq!(*recomputed.rpm_roda, *live.rpm_roda);
+}
+
+#[test]
+fn test_session_warmup_capped() {
+    let mut config = DynoConfig::default();
+    let mut session = DynoSession::new();
+    session.set_raw_capture(true);
+    let idle = SerialData {
+        period: 100,
+        pulse_enc_max: 360,
+        ..Default::default()
+    };
+    // roller diam lama sebelum rekaman dimulai
+    for _ in 0..10_000 {
+        session.push_from_serial(&mut config, idle);
+    }
+    assert_eq!(session.state(), SessionState::Armed);
+    let limit = config.warmup_len(idle.period);
+    assert!(limit > 0 && limit < 10_000);
+    assert_eq!(session.buffer().raw.warmup.len(), limit);
+}
@@ -0,0 +1,122 @@
+use dyno_core::{simulator::*, *};
+
+fn simulate(config: &mut DynoConfig, sim: SimulatorConfig) -> BufferData {
+    let mut buffer = BufferData::new();
+    for serial in DynoSimulator::new(config, sim) {
+        buffer.push_from_serial(config, serial);
+    }
+    buffer
+}
+
+#[test]
+fn test_simulator_deterministic() {
+    let config = DynoConfig::default();
+    let sim = SimulatorConfig {
+        period_jitter_ms: 10,
+        ..SimulatorConfig::from_config(&config)
+    };
+    let first = DynoSimulator::new(&config, sim.clone()).collect::<Vec<_>>();
+    let second = DynoSimulator::new(&config, sim).collect::<Vec<_>>();
+    assert!(!first.is_empty());
+    assert_eq!(first, second);
+    assert!(first.iter().any(|s| s.period != first[0].period));
+}
+
+/// rig with roller and flywheel, heavy enough for a realistic pull
+fn rig_config() -> DynoConfig {
+    DynoConfig {
+        inertia: InertiaModel {
+            components: vec![
+                InertiaComponent {
+                    name: "Roller".to_owned(),
+                    shape: InertiaShape::SolidCylinder {
+                        mass: KiloGram(40.0),
+                        diameter: Metres(0.2),
+                    },
+                    ratio: 1.0,
+                },
+                InertiaComponent {
+                    name: "Flywheel".to_owned(),
+                    shape: InertiaShape::HollowCylinder {
+                        mass: KiloGram(15.0),
+                        outer_diameter: Metres(0.3),
+                        inner_diameter: Metres(0.1),
+                    },
+                    ratio: 1.0,
+                },
+            ],
+        },
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_simulator_run() {
+    let mut config = rig_config();
+    assert!((config.effective_inertia() - 0.3875).abs() < 1e-9);
+    let sim = SimulatorConfig::from_config(&config);
+    let mut simulator = DynoSimulator::new(&config, sim.clone());
+    let mut max_gear = 0;
+    while simulator.step().is_some() {
+        max_gear = Ord::max(max_gear, simulator.gear());
+    }
+    assert!(simulator.is_finished());
+    assert_eq!(max_gear, sim.gear_ratios.len());
+
+    let buffer = simulate(&mut config, sim);
+    let max_rpm_engine = buffer.rpm_engine.max_value();
+    assert!(buffer.speed.max_value().value() > 80.0, "top speed");
+    assert!(
+        max_rpm_engine.value() > 8_000.0 && max_rpm_engine.value() < 10_500.0,
+        "engine rpm {max_rpm_engine}"
+    );
+    // coastdown at the end of the run
+    assert!(buffer.speed.last_value() < buffer.speed.max_value());
+}
+
+#[test]
+fn test_simulator_write_frames() {
+    let config = DynoConfig::default();
+    let mut bytes = Vec::new();
+    let count = DynoSimulator::from_config(&config)
+        .write_to(&mut bytes, false)
+        .unwrap();
+
+    let mut decoder = SerialFrameDecoder::new();
+    let mut decoded = Vec::new();
+    for chunk in bytes.chunks(7) {
+        decoder.feed(chunk);
+        while let Some(data) = decoder.next_data() {
+            decoded.push(data.unwrap().base());
+        }
+    }
+    assert_eq!(decoded.len(), count);
+    assert_eq!(
+        decoded,
+        DynoSimulator::from_config(&config).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_simulator_torque_from_acceleration() {
+    let mut config = rig_config();
+    let sim = SimulatorConfig::from_config(&config);
+    let buffer = simulate(&mut config, sim.clone());
+    let alpha = buffer.angular_acceleration(DerivativeKind::SavitzkyGolay {
+        window: 9,
+        order: 2,
+    });
+    assert_eq!(alpha.len(), buffer.len());
+    // torsi roller dari percepatan sudut (rad/s²) sesuai torsi puncak pada gigi pertama
+    let roller_to_wheel = config.diameter_roller.value() * 0.5 / sim.wheel_radius.value();
+    let expected = sim.peak_torque.value()
+        * sim.gear_ratios[0]
+        * sim.final_ratio
+        * sim.efficiency
+        * roller_to_wheel;
+    let peak = buffer.torque.max_value().value();
+    assert!(
+        (peak - expected).abs() < expected * 0.15,
+        "{peak} != {expected}"
+    );
+}
@@ -78,3 +78,75 @@ fn test_round_decimal() {
     assert_eq!(VALUE.round_decimal(4), 69.697);
     assert_eq!(VALUE.round_decimal(6), 69.69697);
 }
+
+#[test]
+fn test_config_toml() {
+    let mut config = DynoConfig::default();
+    config.inertia.components.push(InertiaComponent {
+        name: "Roller".to_owned(),
+        shape: InertiaShape::SolidCylinder {
+            mass: KiloGram::new(40.0),
+            diameter: Metres::new(0.2),
+        },
+        ratio: 1.0,
+    });
+    let toml = toml::to_string(&config).expect("serialize config to toml");
+    let decoded: DynoConfig = toml::from_str(&toml).expect("deserialize config from toml");
+    assert_eq!(decoded.inertia, config.inertia);
+    assert_eq!(decoded.derivative, config.derivative);
+    assert_eq!(decoded.filter, config.filter);
+    assert_eq!(decoded.effective_inertia(), config.effective_inertia());
+}
+
+#[test]
+fn test_config_legacy_inertia() {
+    // config tanpa inertia model harus menghasilkan torsi yang sama dengan versi sebelumnya
+    let config = DynoConfig::default();
+    assert!(config.inertia.is_empty());
+    let alpha = 12.5;
+    let baseline = 0.5
+        * config.berat_beban.to_float()
+        * config.diameter_roller_beban.to_float().powi(2)
+        * alpha
+        * (config.diameter_gear_beban / config.diameter_roller).to_float();
+    assert!((config.roller_torque(alpha).value() - baseline).abs() < 1e-9);
+    assert_eq!(
+        config.inertia_model().effective_inertia(),
+        config.effective_inertia()
+    );
+}
+
+#[test]
+fn test_config_bincode() {
+    let mut config = DynoConfig::default();
+    config.inertia.components.push(InertiaComponent {
+        name: "Roller".to_owned(),
+        shape: InertiaShape::HollowCylinder {
+            mass: KiloGram::new(40.0),
+            outer_diameter: Metres::new(0.2),
+            inner_diameter: Metres::new(0.1),
+        },
+        ratio: 1.0,
+    });
+    config.filter.torque = FilterKind::Kalman {
+        process_noise: 1e-2,
+        measurement_noise: 1.0,
+    }
+    .build();
+    config.derivative = DerivativeEstimator::new(DerivativeKind::Central { window: 5 });
+    let bytes = config.compress().expect("compress config");
+    let decoded = DynoConfig::decompress(bytes).expect("decompress config");
+    assert_eq!(decoded.inertia, config.inertia);
+    assert_eq!(decoded.derivative, config.derivative);
+    assert_eq!(decoded.filter.torque.kind(), config.filter.torque.kind());
+    assert_eq!(
+        decoded.filter.rpm_roda.kind(),
+        config.filter.rpm_roda.kind()
+    );
+    assert_eq!(decoded.effective_inertia(), config.effective_inertia());
+
+    let default = DynoConfig::default()
+        .compress()
+        .expect("compress default config");
+    assert!(DynoConfig::decompress(default).is_ok());
+}